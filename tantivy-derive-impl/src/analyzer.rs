@@ -0,0 +1,299 @@
+use crate::Field;
+use darling::util::{Flag, SpannedValue};
+use darling::{FromMeta, ast::NestedMeta};
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
+use std::collections::HashSet;
+use syn::Meta;
+
+/// The tokenizers that are registered on every tantivy index.
+const BUILT_IN_TOKENIZERS: &[&str] = &["default", "raw", "en_stem", "whitespace"];
+/// The languages supported by the stemmer.
+const LANGUAGES: &[&str] = &[
+    "arabic",
+    "danish",
+    "dutch",
+    "english",
+    "finnish",
+    "french",
+    "german",
+    "greek",
+    "hungarian",
+    "italian",
+    "norwegian",
+    "portuguese",
+    "romanian",
+    "russian",
+    "spanish",
+    "swedish",
+    "tamil",
+    "turkish",
+];
+/// The languages for which tantivy provides a list of stop words.
+const STOP_WORD_LANGUAGES: &[&str] = &[
+    "danish",
+    "dutch",
+    "english",
+    "finnish",
+    "french",
+    "german",
+    "hungarian",
+    "italian",
+    "norwegian",
+    "portuguese",
+    "russian",
+    "spanish",
+    "swedish",
+];
+
+#[derive(Clone, Debug)]
+struct Language(String);
+
+impl FromMeta for Language {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        if LANGUAGES.contains(&value) {
+            Ok(Self(value.to_string()))
+        } else {
+            Err(darling::Error::unknown_value(value))
+        }
+    }
+}
+
+impl ToTokens for Language {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (first, rest) = self.0.split_at(1);
+        let variant = format_ident!("{}{}", first.to_uppercase(), rest);
+
+        tokens.extend(quote! { tantivy::tokenizer::Language::#variant });
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum Tokenizer {
+    #[default]
+    Simple,
+    Whitespace,
+    Raw,
+}
+
+impl FromMeta for Tokenizer {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "simple" => Ok(Self::Simple),
+            "whitespace" => Ok(Self::Whitespace),
+            "raw" => Ok(Self::Raw),
+            _ => Err(darling::Error::custom(format!(
+                "unknown tokenizer `{value}`, expected one of `simple`, `whitespace` or `raw`"
+            ))),
+        }
+    }
+}
+
+impl ToTokens for Tokenizer {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Simple => quote! { tantivy::tokenizer::SimpleTokenizer::default() },
+            Self::Whitespace => quote! { tantivy::tokenizer::WhitespaceTokenizer::default() },
+            Self::Raw => quote! { tantivy::tokenizer::RawTokenizer::default() },
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Filter {
+    AlphaNumOnly,
+    AsciiFolding,
+    Lowercase,
+    RemoveLong(usize),
+    Stemmer(Language),
+    StopWords(Language),
+}
+
+impl ToTokens for Filter {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::AlphaNumOnly => quote! { tantivy::tokenizer::AlphaNumOnlyFilter },
+            Self::AsciiFolding => quote! { tantivy::tokenizer::AsciiFoldingFilter },
+            Self::Lowercase => quote! { tantivy::tokenizer::LowerCaser },
+            Self::RemoveLong(limit) => {
+                quote! { tantivy::tokenizer::RemoveLongFilter::limit(#limit) }
+            }
+            Self::Stemmer(language) => quote! { tantivy::tokenizer::Stemmer::new(#language) },
+            Self::StopWords(language) => quote! { tantivy_derive::stop_words(#language) },
+        });
+    }
+}
+
+/// The token filters of an analyzer, which are applied in the order in which they are declared.
+#[derive(Clone, Debug, Default)]
+struct Filters(Vec<Filter>);
+
+impl FromMeta for Filters {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let mut filters = Vec::with_capacity(items.len());
+
+        for item in items {
+            let NestedMeta::Meta(meta) = item else {
+                errors.push(darling::Error::unsupported_format("literal").with_span(item));
+                continue;
+            };
+
+            let name = meta
+                .path()
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+
+            let filter = match name.as_str() {
+                "alphanum_only" => Flag::from_meta(meta).map(|_| Filter::AlphaNumOnly),
+                "ascii_folding" => Flag::from_meta(meta).map(|_| Filter::AsciiFolding),
+                "lowercase" => Flag::from_meta(meta).map(|_| Filter::Lowercase),
+                "remove_long" => usize::from_meta(meta).map(Filter::RemoveLong),
+                "stemmer" => Language::from_meta(meta).map(Filter::Stemmer),
+                "stopwords" => Language::from_meta(meta).and_then(|language| {
+                    if STOP_WORD_LANGUAGES.contains(&language.0.as_str()) {
+                        Ok(Filter::StopWords(language))
+                    } else {
+                        Err(darling::Error::custom(format!(
+                            "no stop words are available for `{}`",
+                            language.0
+                        )))
+                    }
+                }),
+                _ => Err(darling::Error::unknown_field_with_alts(
+                    &name,
+                    &[
+                        "alphanum_only",
+                        "ascii_folding",
+                        "lowercase",
+                        "remove_long",
+                        "stemmer",
+                        "stopwords",
+                    ],
+                )),
+            };
+
+            if let Some(filter) = errors.handle(filter.map_err(|e| e.with_span(meta))) {
+                filters.push(filter);
+            }
+        }
+
+        errors.finish_with(Self(filters))
+    }
+}
+
+/// An analyzer that is declared on a document with `#[tantivy(analyzer(name = "...", ...))]`.
+#[derive(Clone, Debug, FromMeta)]
+pub(crate) struct Analyzer {
+    name: SpannedValue<String>,
+    #[darling(default)]
+    tokenizer: Tokenizer,
+    #[darling(default)]
+    filters: Filters,
+}
+
+impl ToTokens for Analyzer {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = self.name.as_str();
+        let tokenizer = &self.tokenizer;
+        let filters = &self.filters.0;
+
+        tokens.extend(quote! {
+            tokenizers.register(
+                #name,
+                tantivy::tokenizer::TextAnalyzer::builder(#tokenizer)
+                    #(
+                        .filter(#filters)
+                    )*
+                    .build(),
+            );
+        });
+    }
+}
+
+/// The name of the tokenizer of a field, which is either given as `tokenizer = "..."` or as
+/// `tokenizer(name = "...", external)` for a tokenizer that is registered on the index by hand.
+#[derive(Clone, Debug)]
+pub(crate) struct TokenizerName {
+    name: SpannedValue<String>,
+    external: bool,
+}
+
+impl TokenizerName {
+    pub(crate) fn as_str(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub(crate) fn span(&self) -> Span {
+        self.name.span()
+    }
+}
+
+impl FromMeta for TokenizerName {
+    fn from_meta(item: &Meta) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct Options {
+            name: SpannedValue<String>,
+            #[darling(default)]
+            external: Flag,
+        }
+
+        match item {
+            Meta::List(_) => Options::from_meta(item).map(|options| Self {
+                name: options.name,
+                external: options.external.is_present(),
+            }),
+            _ => SpannedValue::from_meta(item).map(|name| Self {
+                name,
+                external: false,
+            }),
+        }
+    }
+}
+
+/// Checks that the analyzers have unique names and that every tokenizer that is referenced by a
+/// field is either declared as an analyzer, built into tantivy or marked as `external`.
+pub(crate) fn check_tokenizers(fields: &[&Field], analyzers: &[Analyzer]) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    let mut names = HashSet::new();
+
+    for analyzer in analyzers {
+        if !names.insert(analyzer.name.as_str()) {
+            errors.push(
+                darling::Error::custom(format!(
+                    "analyzer `{}` is declared more than once",
+                    analyzer.name.as_str()
+                ))
+                .with_span(&analyzer.name.span()),
+            );
+        }
+    }
+
+    for field in fields {
+        for tokenizer in [&field.tokenizer, &field.fast_tokenizer]
+            .into_iter()
+            .flatten()
+        {
+            if tokenizer.external
+                || names.contains(tokenizer.as_str())
+                || BUILT_IN_TOKENIZERS.contains(&tokenizer.as_str())
+            {
+                continue;
+            }
+
+            errors.push(
+                darling::Error::custom(format!(
+                    "unknown tokenizer `{}`, expected an analyzer declared with \
+                    `#[tantivy(analyzer(...))]` or one of `default`, `raw`, `en_stem` or \
+                    `whitespace`, or use `tokenizer(name = \"...\", external)` for a tokenizer \
+                    that is registered on the index by hand",
+                    tokenizer.as_str()
+                ))
+                .with_span(&tokenizer.span()),
+            );
+        }
+    }
+
+    errors.finish()
+}
@@ -0,0 +1,414 @@
+use crate::{RenameRule, WRAPPER_TYPES};
+use darling::{FromDeriveInput, FromField, FromMeta, FromVariant, ast, util};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::Ident;
+
+/// How the variants of an enum are represented in the schema.
+#[derive(Clone, Copy, Debug, Default)]
+enum Repr {
+    #[default]
+    String,
+    U64,
+    Facet,
+}
+
+impl FromMeta for Repr {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "string" => Ok(Self::String),
+            "u64" => Ok(Self::U64),
+            "facet" => Ok(Self::Facet),
+            _ => Err(darling::Error::custom(format!(
+                "unknown representation `{value}`, expected one of `string`, `u64` or `facet`"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(tantivy))]
+struct Variant {
+    ident: Ident,
+    #[darling(default)]
+    rename: Option<String>,
+}
+
+impl Variant {
+    /// Returns the name of the variant as stored in the index.
+    fn name(&self, rename_all: Option<RenameRule>) -> String {
+        if let Some(ref rename) = self.rename {
+            return rename.clone();
+        }
+
+        let name = self.ident.to_string();
+
+        match rename_all {
+            Some(rule) => rule.apply_to_variant(&name),
+            None => name,
+        }
+    }
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(tantivy), supports(enum_unit))]
+pub(crate) struct FieldEnum {
+    ident: Ident,
+    generics: syn::Generics,
+    data: ast::Data<Variant, util::Ignored>,
+    #[darling(default)]
+    repr: Repr,
+    #[darling(default)]
+    rename_all: Option<RenameRule>,
+}
+
+impl ToTokens for FieldEnum {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let name = &self.ident;
+
+        let variants = self.data.as_ref().take_enum().expect("must be enum");
+        let idents: Vec<&Ident> = variants.iter().map(|variant| &variant.ident).collect();
+        let names: Vec<String> = variants
+            .iter()
+            .map(|variant| variant.name(self.rename_all))
+            .collect();
+
+        let (add_field, insert, map_value) = match self.repr {
+            Repr::String => (
+                quote! {
+                    options.set_text(false);
+                    options.set_string(true);
+
+                    if options.tokenizer().is_none() {
+                        options.set_tokenizer("raw");
+                    }
+                    <String as tantivy_derive::Field>::add_field(builder, name, options);
+                },
+                quote! {
+                    let value = match value {
+                        #(
+                            Self::#idents => #names,
+                        )*
+                    };
+
+                    document.add_text(field, value);
+                },
+                quote! {
+                    let value = <String as tantivy_derive::Mappable>::map_value(value)?;
+
+                    match value.as_str() {
+                        #(
+                            #names => Ok(Self::#idents),
+                        )*
+                        _ => Err(tantivy_derive::Error::invalid_value::<Self, _>(format!(
+                            "unknown variant `{value}`"
+                        ))),
+                    }
+                },
+            ),
+            Repr::U64 => (
+                quote! {
+                    <u64 as tantivy_derive::Field>::add_field(builder, name, options);
+                },
+                quote! {
+                    let value = match value {
+                        #(
+                            Self::#idents => Self::#idents as u64,
+                        )*
+                    };
+
+                    document.add_u64(field, value);
+                },
+                quote! {
+                    let value = <u64 as tantivy_derive::Mappable>::map_value(value)?;
+
+                    #(
+                        if value == Self::#idents as u64 {
+                            return Ok(Self::#idents);
+                        }
+                    )*
+
+                    Err(tantivy_derive::Error::invalid_value::<Self, _>(format!(
+                        "unknown discriminant `{value}`"
+                    )))
+                },
+            ),
+            Repr::Facet => {
+                let paths: Vec<String> = names
+                    .iter()
+                    .map(|name| {
+                        if name.starts_with('/') {
+                            name.clone()
+                        } else {
+                            format!("/{name}")
+                        }
+                    })
+                    .collect();
+
+                (
+                    quote! {
+                        <tantivy::schema::Facet as tantivy_derive::Field>::add_field(builder, name, options);
+                    },
+                    quote! {
+                        let value = match value {
+                            #(
+                                Self::#idents => #paths,
+                            )*
+                        };
+
+                        document.add_facet(field, tantivy::schema::Facet::from(value));
+                    },
+                    quote! {
+                        let value =
+                            <tantivy::schema::Facet as tantivy_derive::Mappable>::map_value(value)?;
+
+                        match value.as_str() {
+                            #(
+                                #paths => Ok(Self::#idents),
+                            )*
+                            _ => Err(tantivy_derive::Error::invalid_value::<Self, _>(format!(
+                                "unknown facet `{value}`"
+                            ))),
+                        }
+                    },
+                )
+            }
+        };
+
+        tokens.extend(quote! {
+            impl #impl_generics tantivy_derive::Field for #name #ty_generics #where_clause {
+                type Target = Self;
+
+                fn add_field(
+                    builder: &mut tantivy::schema::SchemaBuilder,
+                    name: &str,
+                    mut options: tantivy_derive::FieldOptions,
+                ) {
+                    #add_field
+                }
+
+                fn insert_into_document(
+                    document: &mut tantivy::schema::TantivyDocument,
+                    fields: &tantivy_derive::FieldTable,
+                    field_id: u32,
+                    value: &Self,
+                ) {
+                    let Some(field) = fields.get(field_id) else {
+                        return;
+                    };
+
+                    #insert
+                }
+            }
+
+            impl #impl_generics tantivy_derive::Mappable for #name #ty_generics #where_clause {
+                fn map_value(
+                    value: &tantivy::schema::OwnedValue,
+                ) -> Result<Self::Target, tantivy_derive::Error> {
+                    #map_value
+                }
+            }
+        });
+    }
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(tantivy))]
+struct InnerField {
+    ident: Option<Ident>,
+    ty: syn::Type,
+}
+
+/// A struct with a single field that is declared with `#[tantivy(transparent)]`, which is indexed
+/// and retrieved as the type of its field.
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(tantivy),
+    supports(struct_newtype, struct_tuple, struct_named),
+    and_then = FieldStruct::validate
+)]
+pub(crate) struct FieldStruct {
+    ident: Ident,
+    generics: syn::Generics,
+    data: ast::Data<util::Ignored, InnerField>,
+    transparent: util::Flag,
+    /// Whether the struct is constructed with `From` out of the type that the field is retrieved
+    /// as, which is needed for field types such as `Facet` that are retrieved as another type.
+    #[darling(default)]
+    from_target: util::Flag,
+}
+
+impl FieldStruct {
+    fn validate(self) -> darling::Result<Self> {
+        if !self.transparent.is_present() {
+            return Err(darling::Error::custom(
+                "deriving `Field` for a struct requires `#[tantivy(transparent)]`",
+            )
+            .with_span(&self.ident));
+        }
+
+        let fields = self.data.as_ref().take_struct().expect("must be struct");
+
+        if fields.len() != 1 {
+            return Err(darling::Error::custom(
+                "`transparent` requires a struct with exactly one field",
+            )
+            .with_span(&self.transparent.span()));
+        }
+
+        Ok(self)
+    }
+}
+
+impl ToTokens for FieldStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.ident;
+        let field = self
+            .data
+            .as_ref()
+            .take_struct()
+            .expect("must be struct")
+            .fields
+            .into_iter()
+            .next()
+            .expect("must have one field");
+        let ty = &field.ty;
+        let member: syn::Member = match field.ident {
+            Some(ref ident) => ident.clone().into(),
+            None => syn::Index::from(0).into(),
+        };
+
+        let (construct, bound): (TokenStream, syn::WherePredicate) =
+            if self.from_target.is_present() {
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+
+                (
+                    quote! { Self::from },
+                    syn::parse_quote!(
+                        #name #ty_generics: From<<#ty as tantivy_derive::Field>::Target>
+                    ),
+                )
+            } else {
+                (
+                    quote! { |value| Self { #member: value } },
+                    syn::parse_quote!(#ty: tantivy_derive::Field<Target = #ty>),
+                )
+            };
+
+        // A field that is optional or holds any number of values is not mapped from a single
+        // value, so the struct is extracted, resolved and queried like its field instead.
+        let is_wrapper = matches!(
+            *ty,
+            syn::Type::Path(ref path) if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| WRAPPER_TYPES.contains(&segment.ident.to_string().as_str()))
+        );
+
+        let retrieval_bound: syn::WherePredicate = if is_wrapper {
+            syn::parse_quote!(#ty: tantivy_derive::Extractable + tantivy_derive::Queryable)
+        } else {
+            syn::parse_quote!(#ty: tantivy_derive::Mappable)
+        };
+
+        let mut generics = self.generics.clone();
+        let where_clause = generics.make_where_clause();
+        where_clause.predicates.push(bound);
+        where_clause.predicates.push(retrieval_bound);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let retrieve = if is_wrapper {
+            quote! {
+                impl #impl_generics tantivy_derive::Extractable for #name #ty_generics #where_clause {
+                    fn extract_from_document(
+                        document: &tantivy::schema::TantivyDocument,
+                        fields: &tantivy_derive::FieldTable,
+                        field_id: u32,
+                    ) -> Result<Self::Target, tantivy_derive::Error> {
+                        <#ty as tantivy_derive::Extractable>::extract_from_document(
+                            document,
+                            fields,
+                            field_id,
+                        )
+                        .map(#construct)
+                    }
+                }
+
+                impl #impl_generics tantivy_derive::Resolvable for #name #ty_generics #where_clause {
+                    type Handle = <#ty as tantivy_derive::Resolvable>::Handle;
+
+                    fn resolve(
+                        schema: &tantivy::schema::Schema,
+                        name: &str,
+                    ) -> tantivy::Result<Self::Handle> {
+                        <#ty as tantivy_derive::Resolvable>::resolve(schema, name)
+                    }
+                }
+
+                impl #impl_generics tantivy_derive::Queryable for #name #ty_generics #where_clause {
+                    type Query = <#ty as tantivy_derive::Queryable>::Query;
+
+                    fn query(index: &tantivy::Index, handle: Self::Handle) -> Self::Query {
+                        <#ty as tantivy_derive::Queryable>::query(index, handle)
+                    }
+                }
+            }
+        } else {
+            quote! {
+                impl #impl_generics tantivy_derive::Mappable for #name #ty_generics #where_clause {
+                    fn map_value(
+                        value: &tantivy::schema::OwnedValue,
+                    ) -> Result<Self::Target, tantivy_derive::Error> {
+                        <#ty as tantivy_derive::Mappable>::map_value(value).map(#construct)
+                    }
+                }
+            }
+        };
+
+        tokens.extend(quote! {
+            impl #impl_generics tantivy_derive::Field for #name #ty_generics #where_clause {
+                type Target = Self;
+
+                const SEPARATOR: &'static str = <#ty as tantivy_derive::Field>::SEPARATOR;
+
+                fn add_field(
+                    builder: &mut tantivy::schema::SchemaBuilder,
+                    name: &str,
+                    options: tantivy_derive::FieldOptions,
+                ) {
+                    <#ty as tantivy_derive::Field>::add_field(builder, name, options);
+                }
+
+                fn count_fields() -> u32 {
+                    <#ty as tantivy_derive::Field>::count_fields()
+                }
+
+                fn insert_into_document(
+                    document: &mut tantivy::schema::TantivyDocument,
+                    fields: &tantivy_derive::FieldTable,
+                    field_id: u32,
+                    value: &Self,
+                ) {
+                    <#ty as tantivy_derive::Field>::insert_into_document(
+                        document,
+                        fields,
+                        field_id,
+                        &value.#member,
+                    );
+                }
+
+                fn search_fields(name: &str, fields: &mut Vec<tantivy_derive::SearchField>) {
+                    <#ty as tantivy_derive::Field>::search_fields(name, fields);
+                }
+
+                fn add_tokenizers(tokenizers: &tantivy::tokenizer::TokenizerManager) {
+                    <#ty as tantivy_derive::Field>::add_tokenizers(tokenizers);
+                }
+            }
+
+            #retrieve
+        });
+    }
+}
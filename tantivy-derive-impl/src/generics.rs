@@ -0,0 +1,93 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use std::collections::HashSet;
+use syn::{GenericParam, Generics, Ident, Type};
+
+/// Returns the identifiers of the tokens, including those of nested groups and lifetimes.
+fn idents(tokens: TokenStream, idents: &mut HashSet<Ident>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident);
+            }
+            TokenTree::Group(group) => self::idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the identifier of a generic parameter, which is the name of a lifetime without the
+/// apostrophe.
+fn param_ident(param: &GenericParam) -> &Ident {
+    match *param {
+        GenericParam::Lifetime(ref param) => &param.lifetime.ident,
+        GenericParam::Type(ref param) => &param.ident,
+        GenericParam::Const(ref param) => &param.ident,
+    }
+}
+
+/// Returns whether the type refers to any of the generic parameters.
+pub(crate) fn is_generic(generics: &Generics, ty: &Type) -> bool {
+    let mut used = HashSet::new();
+    idents(ty.to_token_stream(), &mut used);
+
+    generics
+        .params
+        .iter()
+        .any(|param| used.contains(param_ident(param)))
+}
+
+/// Returns the generics with a `#ty: #bound` predicate for every type that refers to one of the
+/// generic parameters, such that the bounds do not have to be declared on the struct.
+pub(crate) fn with_bounds<'a>(
+    generics: &Generics,
+    bounds: impl IntoIterator<Item = (Type, &'a TokenStream)>,
+) -> Generics {
+    let mut bounded = generics.clone();
+    let where_clause = bounded.make_where_clause();
+
+    for (ty, bound) in bounds {
+        if is_generic(generics, &ty) {
+            where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
+        }
+    }
+
+    bounded
+}
+
+/// Returns the type of a `PhantomData` member for the generic parameters that none of the types
+/// refer to, which a generated struct needs since it cannot have unused parameters, or `None` if
+/// every parameter is used.
+pub(crate) fn marker<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+) -> Option<TokenStream> {
+    let mut used = HashSet::new();
+
+    for ty in types {
+        idents(ty.to_token_stream(), &mut used);
+    }
+
+    let unused: Vec<TokenStream> = generics
+        .params
+        .iter()
+        .filter(|param| !used.contains(param_ident(param)))
+        .map(|param| match *param {
+            GenericParam::Lifetime(ref param) => {
+                let lifetime = &param.lifetime;
+                quote! { &#lifetime () }
+            }
+            GenericParam::Type(ref param) => param.ident.to_token_stream(),
+            GenericParam::Const(ref param) => {
+                let ident = &param.ident;
+                quote! { [(); #ident] }
+            }
+        })
+        .collect();
+
+    if unused.is_empty() {
+        return None;
+    }
+
+    Some(quote! { std::marker::PhantomData<fn() -> (#(#unused,)*)> })
+}
@@ -1,43 +1,837 @@
-use darling::{FromDeriveInput, FromField, ast, util};
+use darling::util::{Flag, Override, SpannedValue};
+use darling::{FromDeriveInput, FromField, FromMeta, ast};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
+use syn::ext::IdentExt as _;
 use syn::{DeriveInput, Ident, Type, Visibility, parse_macro_input};
 
+mod analyzer;
+mod field;
+mod generics;
+mod variant;
+mod view;
+
+use analyzer::{Analyzer, TokenizerName};
+
+use variant::Variant;
+use view::View;
+
+/// The types for which the derive macro can tell at compile time which kind of tantivy field they
+/// map to, such that options that do not apply to that kind of field can be rejected.
+const NUMERIC_TYPES: &[&str] = &[
+    "bool",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "isize",
+    "f32",
+    "f64",
+    "NonZeroU8",
+    "NonZeroU16",
+    "NonZeroU32",
+    "NonZeroU64",
+    "NonZeroUsize",
+    "NonZeroI8",
+    "NonZeroI16",
+    "NonZeroI32",
+    "NonZeroI64",
+    "NonZeroIsize",
+    "FixedDecimal",
+];
+const DATE_TYPES: &[&str] = &["DateTime", "NaiveDate", "Timestamp"];
+/// The types that wrap the type of a field, which are looked through to determine the kind of
+/// field.
+const WRAPPER_TYPES: &[&str] = &["Option", "Vec", "VecDeque", "BTreeSet", "HashSet"];
+
+#[derive(Clone, Copy, Debug)]
+enum IndexOption {
+    Basic,
+    Frequency,
+    FrequencyAndPosition,
+}
+
+impl FromMeta for IndexOption {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "basic" => Ok(Self::Basic),
+            "frequency" => Ok(Self::Frequency),
+            "frequency-and-position" => Ok(Self::FrequencyAndPosition),
+            _ => Err(darling::Error::custom(format!(
+                "unknown index option `{value}`, expected one of `basic`, `frequency` or \
+                `frequency-and-position`"
+            ))),
+        }
+    }
+}
+
+impl ToTokens for IndexOption {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Basic => quote! { IndexRecordOption::Basic },
+            Self::Frequency => quote! { IndexRecordOption::WithFreqs },
+            Self::FrequencyAndPosition => quote! { IndexRecordOption::WithFreqsAndPositions },
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Precision {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl FromMeta for Precision {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "seconds" => Ok(Self::Seconds),
+            "milliseconds" => Ok(Self::Milliseconds),
+            "microseconds" => Ok(Self::Microseconds),
+            "nanoseconds" => Ok(Self::Nanoseconds),
+            _ => Err(darling::Error::custom(format!(
+                "unknown precision `{value}`, expected one of `seconds`, `milliseconds`, \
+                `microseconds` or `nanoseconds`"
+            ))),
+        }
+    }
+}
+
+impl ToTokens for Precision {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::Seconds => quote! { DateTimePrecision::Seconds },
+            Self::Milliseconds => quote! { DateTimePrecision::Milliseconds },
+            Self::Microseconds => quote! { DateTimePrecision::Microseconds },
+            Self::Nanoseconds => quote! { DateTimePrecision::Nanoseconds },
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Applies the rule to a `PascalCase` name, such as the name of an enum variant.
+    fn apply_to_variant(self, name: &str) -> String {
+        match self {
+            Self::Pascal => name.to_string(),
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            _ => {
+                let mut snake_case = String::with_capacity(name.len());
+
+                for (i, c) in name.char_indices() {
+                    if c.is_uppercase() && i > 0 {
+                        snake_case.push('_');
+                    }
+
+                    snake_case.extend(c.to_lowercase());
+                }
+
+                self.apply(&snake_case)
+            }
+        }
+    }
+
+    /// Applies the rule to a `snake_case` name.
+    fn apply(self, name: &str) -> String {
+        let pascal_case = || {
+            name.split('_')
+                .map(|word| {
+                    let mut chars = word.chars();
+
+                    match chars.next() {
+                        Some(c) => c.to_uppercase().chain(chars).collect(),
+                        None => String::new(),
+                    }
+                })
+                .collect::<String>()
+        };
+
+        match self {
+            Self::Lower | Self::Snake => name.to_string(),
+            Self::Upper | Self::ScreamingSnake => name.to_uppercase(),
+            Self::Pascal => pascal_case(),
+            Self::Camel => {
+                let name = pascal_case();
+                let mut chars = name.chars();
+
+                match chars.next() {
+                    Some(c) => c.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            Self::Kebab => name.replace('_', "-"),
+            Self::ScreamingKebab => name.to_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(darling::Error::custom(format!(
+                "unknown rename rule `{value}`, expected one of `lowercase`, `UPPERCASE`, \
+                `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or \
+                `SCREAMING-KEBAB-CASE`"
+            ))),
+        }
+    }
+}
+
+/// The settings for fuzzy term queries on a field.
+#[derive(Clone, Debug, FromMeta)]
+#[darling(default)]
+struct Fuzzy {
+    distance: u8,
+    prefix: Flag,
+    transpose_cost_one: Flag,
+}
+
+impl Default for Fuzzy {
+    fn default() -> Self {
+        Self {
+            distance: 1,
+            prefix: Flag::default(),
+            transpose_cost_one: Flag::default(),
+        }
+    }
+}
+
+impl ToTokens for Fuzzy {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let distance = self.distance;
+        let prefix = self.prefix.is_present();
+        let transpose_cost_one = self.transpose_cost_one.is_present();
+
+        tokens.extend(quote! {
+            tantivy_derive::Fuzzy {
+                distance: #distance,
+                prefix: #prefix,
+                transpose_cost_one: #transpose_cost_one,
+            }
+        });
+    }
+}
+
+/// The options that can be specified on the struct itself.
+#[derive(Debug, Default, FromMeta)]
+struct ContainerOptions {
+    #[darling(default)]
+    rename_all: Option<RenameRule>,
+    /// The separator between the name of a nested document and the names of its fields.
+    #[darling(default)]
+    separator: Option<String>,
+    /// The name of the field that stores the variant of an enum.
+    #[darling(default)]
+    tag: Option<SpannedValue<String>>,
+    /// Whether the query parser requires all terms of a query to match.
+    #[darling(default)]
+    conjunction_by_default: Flag,
+    /// The version of the schema, which is recorded in the index when committing.
+    #[darling(default)]
+    version: Option<u32>,
+    #[darling(multiple, rename = "analyzer")]
+    analyzers: Vec<Analyzer>,
+    #[darling(multiple, rename = "view")]
+    views: Vec<View>,
+}
+
+impl ContainerOptions {
+    fn separator(&self) -> &str {
+        self.separator.as_deref().unwrap_or(".")
+    }
+
+    fn tag(&self) -> &str {
+        self.tag.as_deref().map_or("type", String::as_str)
+    }
+}
+
+/// Applies the `rename_all` rule to every field that is not renamed explicitly.
+fn rename_struct_fields(
+    fields: ast::Fields<Field>,
+    rename_all: Option<RenameRule>,
+) -> ast::Fields<Field> {
+    let Some(rule) = rename_all else {
+        return fields;
+    };
+
+    fields.map(|mut field| {
+        if field.rename.is_none() {
+            field.rename = Some(SpannedValue::new(
+                rule.apply(&field.name()),
+                proc_macro2::Span::call_site(),
+            ));
+        }
+
+        field
+    })
+}
+
+/// Applies the `rename_all` rule of the container to every field of a struct, or to every variant
+/// of an enum, that is not renamed explicitly.
+fn rename_fields(
+    data: ast::Data<Variant, Field>,
+    options: &ContainerOptions,
+) -> darling::Result<ast::Data<Variant, Field>> {
+    match data {
+        ast::Data::Struct(fields) => {
+            if let Some(ref tag) = options.tag {
+                return Err(darling::Error::custom("`tag` is only supported on enums")
+                    .with_span(&tag.span()));
+            }
+
+            Ok(ast::Data::Struct(rename_struct_fields(
+                fields,
+                options.rename_all,
+            )))
+        }
+        ast::Data::Enum(variants) => {
+            variant::rename_variants(variants, options).map(ast::Data::Enum)
+        }
+    }
+}
+
+/// Returns the fields of a struct, or the fields of every variant of an enum, that are not
+/// skipped.
+fn all_fields(data: &ast::Data<Variant, Field>) -> Vec<&Field> {
+    let fields: Vec<&Field> = match data {
+        ast::Data::Struct(fields) => fields.iter().collect(),
+        ast::Data::Enum(variants) => variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+    };
+
+    fields
+        .into_iter()
+        .filter(|field| !field.skip.is_present())
+        .collect()
+}
+
+/// Returns an expression that evaluates to the name of a field in the schema, where `name` is the
+/// name of the document that the field belongs to, which is empty for the top-level document.
+fn join_path(field_name: &str, separator: &str) -> TokenStream {
+    quote! {
+        if name.is_empty() {
+            #field_name.to_string()
+        } else {
+            format!("{}{}{}", name, #separator, #field_name)
+        }
+    }
+}
+
 #[derive(Debug, FromField)]
-#[darling(attributes(tantivy))]
+#[darling(attributes(tantivy), and_then = Self::validate)]
 struct Field {
     vis: Visibility,
     ident: Option<Ident>,
     ty: Type,
     #[darling(default)]
-    coerce: bool,
+    coerce: Flag,
+    #[darling(default)]
+    expand_dots: Flag,
+    #[darling(default)]
+    fast: Flag,
+    #[darling(default)]
+    fieldnorms: Flag,
     #[darling(default)]
-    fast: bool,
+    indexed: Flag,
     #[darling(default)]
-    fieldnorms: bool,
+    stored: Flag,
     #[darling(default)]
-    indexed: bool,
+    store_target: Option<SpannedValue<Type>>,
     #[darling(default)]
-    stored: bool,
+    string: Flag,
     #[darling(default)]
-    store_target: Option<String>,
+    text: Flag,
     #[darling(default)]
-    string: bool,
+    fast_tokenizer: Option<TokenizerName>,
     #[darling(default)]
-    text: bool,
+    tokenizer: Option<TokenizerName>,
     #[darling(default)]
-    fast_tokenizer: Option<String>,
+    index_option: Option<SpannedValue<IndexOption>>,
     #[darling(default)]
-    tokenizer: Option<String>,
+    precision: Option<SpannedValue<Precision>>,
     #[darling(default)]
-    index_option: Option<String>,
+    rename: Option<SpannedValue<String>>,
     #[darling(default)]
-    precision: Option<String>,
+    flatten: Flag,
+    #[darling(default)]
+    id: Flag,
+    #[darling(default)]
+    skip: Flag,
+    #[darling(default)]
+    with: Option<SpannedValue<syn::Path>>,
+    #[darling(default)]
+    as_string: Flag,
+    #[darling(default)]
+    via: Option<SpannedValue<Type>>,
+    #[darling(default)]
+    default: Option<SpannedValue<Override<syn::Path>>>,
+    #[darling(default)]
+    search_default: Flag,
+    #[darling(default)]
+    boost: Option<SpannedValue<f32>>,
+    #[darling(default)]
+    fuzzy: Option<SpannedValue<Override<Fuzzy>>>,
+}
+
+/// Returns the name of the type that ends up in the schema, looking through `Option<T>` and
+/// collections such as `Vec<T>`.
+fn base_type_name(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let name = segment.ident.to_string();
+
+    if WRAPPER_TYPES.contains(&name.as_str())
+        && let syn::PathArguments::AngleBracketed(ref args) = segment.arguments
+        && let Some(syn::GenericArgument::Type(ty)) = args.args.first()
+    {
+        return base_type_name(ty);
+    }
+
+    Some(name)
 }
 
 impl Field {
+    fn validate(self) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+
+        if let Some(ref rename) = self.rename
+            && rename.is_empty()
+        {
+            errors
+                .push(darling::Error::custom("`rename` cannot be empty").with_span(&rename.span()));
+        }
+
+        if let Some(ref rename) = self.rename
+            && self.flatten.is_present()
+        {
+            errors.push(
+                darling::Error::custom("`rename` and `flatten` cannot be used together")
+                    .with_span(&rename.span()),
+            );
+        }
+
+        if self.string.is_present() && self.text.is_present() {
+            errors.push(
+                darling::Error::custom("`string` and `text` cannot be used together")
+                    .with_span(&self.text.span()),
+            );
+        }
+
+        if let Some(ref store_target) = self.store_target
+            && !self.stored.is_present()
+        {
+            errors.push(
+                darling::Error::custom("`store_target` requires the field to be `stored`")
+                    .with_span(&store_target.span()),
+            );
+        }
+
+        if let Some(ref default) = self.default
+            && !self.stored.is_present()
+            && !self.skip.is_present()
+        {
+            errors.push(
+                darling::Error::custom("`default` requires the field to be `stored` or `skip`")
+                    .with_span(&default.span()),
+            );
+        }
+
+        if self.skip.is_present() {
+            let options = [
+                ("coerce", self.coerce.is_present()),
+                ("expand_dots", self.expand_dots.is_present()),
+                ("fast", self.fast.is_present()),
+                ("fieldnorms", self.fieldnorms.is_present()),
+                ("indexed", self.indexed.is_present()),
+                ("stored", self.stored.is_present()),
+                ("store_target", self.store_target.is_some()),
+                ("string", self.string.is_present()),
+                ("text", self.text.is_present()),
+                ("fast_tokenizer", self.fast_tokenizer.is_some()),
+                ("tokenizer", self.tokenizer.is_some()),
+                ("index_option", self.index_option.is_some()),
+                ("precision", self.precision.is_some()),
+                ("rename", self.rename.is_some()),
+                ("flatten", self.flatten.is_present()),
+                ("id", self.id.is_present()),
+                ("search_default", self.search_default.is_present()),
+                ("boost", self.boost.is_some()),
+                ("fuzzy", self.fuzzy.is_some()),
+                ("with", self.with.is_some()),
+                ("as_string", self.as_string.is_present()),
+                ("via", self.via.is_some()),
+            ];
+
+            for (option, _) in options.into_iter().filter(|(_, present)| *present) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{option}` cannot be used on a field that is skipped"
+                    ))
+                    .with_span(&self.skip.span()),
+                );
+            }
+
+            return errors.finish_with(self);
+        }
+
+        if let Some(ref fuzzy) = self.fuzzy
+            && fuzzy.as_ref().clone().unwrap_or_default().distance > 2
+        {
+            errors.push(
+                darling::Error::custom("the `fuzzy` distance cannot be larger than 2")
+                    .with_span(&fuzzy.span()),
+            );
+        }
+
+        if self.id.is_present() && self.flatten.is_present() {
+            errors.push(
+                darling::Error::custom("`id` and `flatten` cannot be used together")
+                    .with_span(&self.id.span()),
+            );
+        }
+
+        if self.id.is_present()
+            && let Type::Path(ref path) = self.ty
+            && let Some(segment) = path.path.segments.last()
+            && WRAPPER_TYPES.contains(&segment.ident.to_string().as_str())
+        {
+            errors.push(
+                darling::Error::custom(format!(
+                    "`id` requires a field with a single value, but `{}` can hold any number of values",
+                    segment.ident
+                ))
+                .with_span(&self.id.span()),
+            );
+        }
+
+        if let Some(ref via) = self.via
+            && self.as_string.is_present()
+        {
+            errors.push(
+                darling::Error::custom("`as_string` and `via` cannot be used together")
+                    .with_span(&via.span()),
+            );
+        }
+
+        if let Some(span) = self.adapter_span() {
+            let options = [
+                ("expand_dots", self.expand_dots.is_present()),
+                ("flatten", self.flatten.is_present()),
+                ("with", self.with.is_some()),
+            ];
+
+            for (option, _) in options.into_iter().filter(|(_, present)| *present) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{option}` cannot be used together with `as_string` or `via`"
+                    ))
+                    .with_span(&span),
+                );
+            }
+
+            if let Type::Path(ref path) = self.ty
+                && let Some(segment) = path.path.segments.last()
+                && WRAPPER_TYPES.contains(&segment.ident.to_string().as_str())
+            {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`as_string` and `via` require a field with a single value, but `{}` can \
+                        hold any number of values",
+                        segment.ident
+                    ))
+                    .with_span(&span),
+                );
+            }
+
+            if self.id.is_present() {
+                errors.push(
+                    darling::Error::custom(
+                        "`id` cannot be used together with `as_string` or `via`, use \
+                        `DisplayFromStr<T>` or `Via<T, R>` as the type of the field instead",
+                    )
+                    .with_span(&span),
+                );
+            }
+
+            // The representation decides which kind of field is added to the schema.
+            return errors.finish_with(self);
+        }
+
+        if let Some(ref with) = self.with {
+            let options = [
+                ("flatten", self.flatten.is_present()),
+                ("id", self.id.is_present()),
+            ];
+
+            for (option, _) in options.into_iter().filter(|(_, present)| *present) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{option}` and `with` cannot be used together"
+                    ))
+                    .with_span(&with.span()),
+                );
+            }
+
+            // The module decides which kind of field is added to the schema.
+            return errors.finish_with(self);
+        }
+
+        if self.expand_dots.is_present() && base_type_name(&self.ty).as_deref() != Some("Json") {
+            errors.push(
+                darling::Error::custom("`expand_dots` is only supported on `Json<T>` fields")
+                    .with_span(&self.expand_dots.span()),
+            );
+        }
+
+        let Some(ty) = base_type_name(&self.ty) else {
+            return errors.finish_with(self);
+        };
+
+        let kind = if NUMERIC_TYPES.contains(&ty.as_str()) {
+            Some("numeric")
+        } else if DATE_TYPES.contains(&ty.as_str()) {
+            Some("date")
+        } else {
+            None
+        };
+
+        if let Some(kind) = kind {
+            let text_options = [
+                (
+                    "string",
+                    self.string.is_present().then(|| self.string.span()),
+                ),
+                ("text", self.text.is_present().then(|| self.text.span())),
+                ("tokenizer", self.tokenizer.as_ref().map(|v| v.span())),
+                (
+                    "fast_tokenizer",
+                    self.fast_tokenizer.as_ref().map(|v| v.span()),
+                ),
+                ("index_option", self.index_option.as_ref().map(|v| v.span())),
+                ("fuzzy", self.fuzzy.as_ref().map(|v| v.span())),
+            ];
+
+            for (option, span) in text_options {
+                let Some(span) = span else {
+                    continue;
+                };
+
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{option}` is only supported on text fields, but `{ty}` is a {kind} field"
+                    ))
+                    .with_span(&span),
+                );
+            }
+        }
+
+        if self.id.is_present() {
+            if kind.is_some() && !self.indexed.is_present() {
+                errors.push(
+                    darling::Error::custom("the `id` field must be `indexed`")
+                        .with_span(&self.id.span()),
+                );
+            }
+
+            let tokenized = self.text.is_present()
+                || self
+                    .tokenizer
+                    .as_ref()
+                    .is_some_and(|tokenizer| tokenizer.as_str() != "raw");
+
+            if kind.is_none() && (!self.string.is_present() || tokenized) {
+                errors.push(
+                    darling::Error::custom("the `id` field must be an untokenized `string` field")
+                        .with_span(&self.id.span()),
+                );
+            }
+        }
+
+        if let Some(ref precision) = self.precision
+            && kind != Some("date")
+        {
+            errors.push(
+                darling::Error::custom(format!(
+                    "`precision` is only supported on date fields, but `{ty}` is not a date field"
+                ))
+                .with_span(&precision.span()),
+            );
+        }
+
+        errors.finish_with(self)
+    }
+
+    /// Returns the name of the field in the schema.
+    fn name(&self) -> String {
+        if let Some(ref rename) = self.rename {
+            return rename.to_string();
+        }
+
+        let name = self
+            .ident
+            .as_ref()
+            .expect("must be a named struct")
+            .to_string();
+
+        name.trim_start_matches('_').to_string()
+    }
+
+    /// Returns an expression that evaluates to the name of the field in the schema, where `name`
+    /// is the name of the document that the field belongs to, which is empty for the top-level
+    /// document.
+    fn path(&self, separator: &str) -> TokenStream {
+        if self.flatten.is_present() {
+            return quote! { name.to_string() };
+        }
+
+        join_path(&self.name(), separator)
+    }
+
+    /// Returns the span of `as_string` or `via` if the field is indexed through an adapter.
+    fn adapter_span(&self) -> Option<proc_macro2::Span> {
+        if self.as_string.is_present() {
+            Some(self.as_string.span())
+        } else {
+            self.via.as_ref().map(|via| via.span())
+        }
+    }
+
+    /// Returns the type whose `Field` implementation is used for the field, which is the adapter
+    /// for fields that are indexed `as_string` or `via` another type.
+    fn field_ty(&self) -> Type {
+        let ty = &self.ty;
+
+        if self.as_string.is_present() {
+            syn::parse_quote!(tantivy_derive::DisplayFromStr<#ty>)
+        } else if let Some(ref via) = self.via {
+            let via = via.as_ref();
+            syn::parse_quote!(tantivy_derive::Via<#ty, #via>)
+        } else {
+            ty.clone()
+        }
+    }
+
+    /// Returns the expression that evaluates to the number of schema fields that the field adds.
+    fn parse_count(&self) -> TokenStream {
+        let ty = self.field_ty();
+
+        if self.with.is_some() {
+            quote! { 1 }
+        } else {
+            quote! { <#ty>::count_fields() }
+        }
+    }
+
+    /// Returns the statement that inserts the value of the field into a document.
+    fn parse_insert(&self, value: TokenStream) -> TokenStream {
+        let ty = self.field_ty();
+
+        if self.adapter_span().is_some() {
+            return quote! {
+                <#ty>::insert_value(document, fields, field_id, #value);
+            };
+        }
+
+        match self.with {
+            Some(ref with) => {
+                let with = with.as_ref();
+                quote! {
+                    if let Some(field) = fields.get(field_id) {
+                        #with::insert(document, field, #value);
+                    }
+                }
+            }
+            None => quote! {
+                <#ty>::insert_into_document(document, fields, field_id, #value);
+            },
+        }
+    }
+
+    /// Returns the expression that evaluates to the `default` value of a field.
+    fn parse_default(default: &Override<syn::Path>) -> TokenStream {
+        match *default {
+            Override::Inherit => quote! { Default::default() },
+            Override::Explicit(ref path) => quote! { #path() },
+        }
+    }
+
+    /// Returns the statement that extracts the stored value of the field, which falls back to the
+    /// `default` value of the field if the value is missing from the document.
+    fn parse_extract(&self) -> TokenStream {
+        let ident = &self.ident;
+        let ty = self.field_ty();
+        let name = if self.flatten.is_present() {
+            String::new()
+        } else {
+            self.name()
+        };
+
+        let extract = match self.with {
+            Some(ref with) => {
+                let with = with.as_ref();
+                quote! {
+                    tantivy_derive::extract_value(document, fields, field_id, #with::map_value)
+                }
+            }
+            None => quote! { <#ty>::extract_from_document(document, fields, field_id) },
+        };
+
+        // The error of a nested document is joined with the separator of that document, such that
+        // it names the field as it appears in the schema.
+        let separator = match self.with {
+            Some(_) => quote! { "." },
+            None => quote! { <#ty as tantivy_derive::Field>::SEPARATOR },
+        };
+
+        let Some(ref default) = self.default else {
+            return quote! {
+                let #ident = #extract.map_err(|e| e.in_field(#name, #separator))?;
+            };
+        };
+
+        let default = Self::parse_default(default);
+
+        quote! {
+            let #ident = match #extract {
+                Err(tantivy_derive::Error::MissingField { .. }) => #default,
+                result => result.map_err(|e| e.in_field(#name, #separator))?,
+            };
+        }
+    }
+
     fn parse(
         &self,
+        separator: &str,
     ) -> (
         TokenStream,
         TokenStream,
@@ -47,8 +841,8 @@ impl Field {
     ) {
         let Field {
             ident,
-            ty,
             coerce,
+            expand_dots,
             fast,
             fieldnorms,
             indexed,
@@ -62,110 +856,139 @@ impl Field {
             ..
         } = self;
 
-        let name = ident.as_ref().expect("must be a named struct").to_string();
-        let name = name.trim_start_matches('_');
+        if self.skip.is_present() {
+            let Some(ref default) = self.default else {
+                return Default::default();
+            };
+            let default = Self::parse_default(default);
+
+            return (
+                TokenStream::new(),
+                TokenStream::new(),
+                quote! { let #ident = #default; },
+                quote! { #ident, },
+                TokenStream::new(),
+            );
+        }
+
+        let path = self.path(separator);
+
+        let field_count = self.parse_count();
 
         let count_token = quote! {
-            count += <#ty>::count_fields();
+            count += #field_count;
         };
 
-        let from_token = if *stored {
+        let from_token = if stored.is_present() {
+            let extract_token = self.parse_extract();
+
             quote! {
-                let #ident = <#ty>::extract_from_document(&document, field_id)?;
-                field_id += <#ty>::count_fields();
+                #extract_token
+                field_id += #field_count;
             }
         } else {
             quote! {
-                field_id += <#ty>::count_fields();
+                field_id += #field_count;
             }
         };
 
-        let field_token = if *stored {
+        let field_token = if stored.is_present() {
             quote! { #ident, }
         } else {
             TokenStream::new()
         };
 
-        let coerce = if *coerce {
+        let coerce = if coerce.is_present() {
             quote! { options.set_coerce(true); }
         } else {
             TokenStream::new()
         };
 
-        let fast = if *fast {
+        let expand_dots = if expand_dots.is_present() {
+            quote! { options.set_expand_dots(true); }
+        } else {
+            TokenStream::new()
+        };
+
+        let fast = if fast.is_present() {
             quote! { options.set_fast(true); }
         } else {
             TokenStream::new()
         };
 
-        let fieldnorms = if *fieldnorms {
+        let fieldnorms = if fieldnorms.is_present() {
             quote! { options.set_fieldnorms(true); }
         } else {
             TokenStream::new()
         };
 
-        let indexed = if *indexed {
+        let indexed = if indexed.is_present() {
             quote! { options.set_indexed(true); }
         } else {
             TokenStream::new()
         };
 
-        let stored = if *stored {
+        let stored = if stored.is_present() {
             quote! { options.set_stored(true); }
         } else {
             TokenStream::new()
         };
 
-        let string = if *string {
+        let string = if string.is_present() {
             quote! { options.set_string(true); }
         } else {
             TokenStream::new()
         };
 
-        let text = if *text {
+        let text = if text.is_present() {
             quote! { options.set_text(true); }
         } else {
             TokenStream::new()
         };
 
-        let index_option = match index_option.as_ref().map(|s| s.as_str()) {
-            Some("basic") => quote! { options.set_index_option(IndexRecordOption::Basic); },
-            Some("frequency") => quote! { options.set_index_option(IndexRecordOption::WithFreqs); },
-            Some("frequency-and-position") => {
-                quote! { options.set_index_option(IndexRecordOption::WithFreqsAndPositions); }
-            }
-            _ => TokenStream::new(),
+        let index_option = if let Some(index_option) = index_option {
+            let index_option = index_option.as_ref();
+            quote! { options.set_index_option(#index_option); }
+        } else {
+            TokenStream::new()
         };
 
         let fast_tokenizer = if let Some(tokenizer) = fast_tokenizer {
+            let tokenizer = tokenizer.as_str();
             quote! { options.set_fast_tokenizer(#tokenizer); }
         } else {
             TokenStream::new()
         };
 
         let tokenizer = if let Some(tokenizer) = tokenizer {
+            let tokenizer = tokenizer.as_str();
             quote! { options.set_tokenizer(#tokenizer); }
         } else {
             TokenStream::new()
         };
 
-        let precision = match precision.as_ref().map(|s| s.as_str()) {
-            Some("seconds") => quote! { options.set_precision(DateTimePrecision::Seconds); },
-            Some("milliseconds") => {
-                quote! { options.set_precision(DateTimePrecision::Milliseconds); }
-            }
-            Some("microseconds") => {
-                quote! { options.set_precision(DateTimePrecision::Microseconds); }
+        let precision = if let Some(precision) = precision {
+            let precision = precision.as_ref();
+            quote! { options.set_precision(#precision); }
+        } else {
+            TokenStream::new()
+        };
+
+        let add_field = match self.with {
+            Some(ref with) => {
+                let with = with.as_ref();
+                quote! { #with::add_field(builder, &#path, options); }
             }
-            Some("nanoseconds") => {
-                quote! { options.set_precision(DateTimePrecision::Nanoseconds); }
+            None => {
+                let ty = self.field_ty();
+                quote! { <#ty>::add_field(builder, &#path, options); }
             }
-            _ => TokenStream::new(),
         };
 
         let schema_token = quote! {
             let mut options: tantivy_derive::FieldOptions = Default::default();
             #coerce
+            #expand_dots
             #fast
             #fieldnorms
             #indexed
@@ -176,12 +999,13 @@ impl Field {
             #tokenizer
             #index_option
             #precision
-            <#ty>::add_field(builder, #name, options);
+            #add_field
         };
 
+        let insert_token = self.parse_insert(quote! { &value.#ident });
         let into_token = quote! {
-            <#ty>::insert_into_document(document, field_id, &value.#ident);
-            field_id += <#ty>::count_fields();
+            #insert_token
+            field_id += #field_count;
         };
 
         (
@@ -193,99 +1017,425 @@ impl Field {
         )
     }
 
-    fn parse_stored(&self) -> TokenStream {
+    fn parse_search(&self, separator: &str) -> TokenStream {
+        let ty = self.field_ty();
         let Field {
-            vis,
-            ident,
-            ty,
-            stored,
-            store_target,
+            search_default,
+            boost,
+            fuzzy,
             ..
         } = self;
-
-        if *stored {
-            if let Some(target) = store_target {
-                quote! {
-                    #vis #ident: #target,
+        let path = self.path(separator);
+
+        let search_field = if search_default.is_present() || boost.is_some() || fuzzy.is_some() {
+            let default = search_default.is_present();
+            let boost = match boost {
+                Some(boost) => {
+                    let boost = **boost;
+                    quote! { Some(#boost) }
                 }
-            } else {
-                quote! {
-                    #vis #ident: #ty,
+                None => quote! { None },
+            };
+            let fuzzy = match fuzzy {
+                Some(fuzzy) => {
+                    let fuzzy = fuzzy.as_ref().clone().unwrap_or_default();
+                    quote! { Some(#fuzzy) }
                 }
+                None => quote! { None },
+            };
+
+            quote! {
+                fields.push(tantivy_derive::SearchField {
+                    name: #path,
+                    default: #default,
+                    boost: #boost,
+                    fuzzy: #fuzzy,
+                });
             }
         } else {
             TokenStream::new()
+        };
+
+        if self.with.is_some() {
+            return search_field;
+        }
+
+        quote! {
+            #search_field
+            <#ty>::search_fields(&#path, fields);
+        }
+    }
+
+    fn parse_handle(
+        &self,
+        separator: &str,
+    ) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+        let ident = self.ident.as_ref().expect("must be a named struct");
+
+        self.parse_handle_as(&self.vis, ident, separator)
+    }
+
+    /// Returns the member of the handle struct for the field, the expression that resolves it,
+    /// the method of the query struct that queries it and the entry of the member in the `Debug`
+    /// output, where fields that are converted `with` a module are resolved to a plain schema
+    /// field and cannot be queried.
+    fn parse_handle_as(
+        &self,
+        vis: &Visibility,
+        member: &Ident,
+        separator: &str,
+    ) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+        let path = self.path(separator);
+
+        if self.with.is_none() {
+            return parse_handle(vis, member, &self.field_ty(), &path);
+        }
+
+        (
+            quote! { #vis #member: tantivy::schema::Field, },
+            quote! { #member: schema.get_field(&#path)?, },
+            TokenStream::new(),
+            parse_debug(member),
+        )
+    }
+
+    /// Returns the type of the field in the stored document, or `None` if the field is not part
+    /// of the stored document.
+    fn stored_ty(&self) -> Option<Type> {
+        let ty = &self.ty;
+
+        if self.skip.is_present() {
+            return self.default.as_ref().map(|_| ty.clone());
+        }
+
+        if !self.stored.is_present() {
+            return None;
+        }
+
+        if let Some(ref target) = self.store_target {
+            Some(target.as_ref().clone())
+        } else if self.with.is_some() {
+            Some(ty.clone())
+        } else {
+            let ty = self.field_ty();
+            Some(syn::parse_quote!(<#ty as tantivy_derive::Field>::Target))
         }
     }
+
+    /// Returns the type that must implement `Field` for the type of the field in the stored
+    /// document to be known.
+    fn stored_bound(&self) -> Option<Type> {
+        let is_target = self.stored.is_present()
+            && !self.skip.is_present()
+            && self.with.is_none()
+            && self.store_target.is_none();
+
+        is_target.then(|| self.field_ty())
+    }
+
+    fn parse_stored(&self) -> TokenStream {
+        let Field { vis, ident, .. } = self;
+
+        match self.stored_ty() {
+            Some(ty) => quote! {
+                #vis #ident: #ty,
+            },
+            None => TokenStream::new(),
+        }
+    }
+}
+
+/// Returns the generics of the implementations for a document, with a bound on the types of the
+/// fields that refer to a generic parameter: `Extractable` for stored fields and `Field` for the
+/// others.
+fn document_generics(generics: &syn::Generics, data: &ast::Data<Variant, Field>) -> syn::Generics {
+    let field_bound = quote! { tantivy_derive::Field };
+    let extractable_bound = quote! { tantivy_derive::Extractable };
+
+    generics::with_bounds(
+        generics,
+        all_fields(data)
+            .into_iter()
+            .filter(|field| !field.skip.is_present() && field.with.is_none())
+            .map(|field| {
+                let bound = if field.stored.is_present() {
+                    &extractable_bound
+                } else {
+                    &field_bound
+                };
+
+                (field.field_ty(), bound)
+            }),
+    )
+}
+
+/// Returns the generics of a struct that contains the stored fields, with a `Field` bound on the
+/// types of the fields that refer to a generic parameter.
+fn stored_generics<'a>(
+    generics: &syn::Generics,
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Generics {
+    let bound = quote! { tantivy_derive::Field };
+
+    generics::with_bounds(
+        generics,
+        fields
+            .into_iter()
+            .filter_map(Field::stored_bound)
+            .map(|ty| (ty, &bound)),
+    )
+}
+
+/// Returns the type of the marker of a struct that contains the stored fields, which holds the
+/// generic parameters that are not used by any of the fields.
+fn stored_marker<'a>(
+    generics: &syn::Generics,
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> Option<TokenStream> {
+    let types: Vec<Type> = fields.into_iter().filter_map(Field::stored_ty).collect();
+
+    generics::marker(generics, &types)
 }
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(tantivy), supports(struct_named))]
+#[darling(
+    attributes(tantivy),
+    supports(struct_named, enum_named, enum_unit),
+    and_then = Self::validate
+)]
 struct Document {
     ident: Ident,
     generics: syn::Generics,
-    data: ast::Data<util::Ignored, Field>,
+    data: ast::Data<Variant, Field>,
+    #[darling(flatten)]
+    options: ContainerOptions,
+    /// The name of the stored struct, which is set by `#[tantivy_document(name = ...)]` and
+    /// defaults to the name of the document prefixed with `Stored`.
+    #[darling(skip)]
+    stored_name: Option<Ident>,
+}
+
+impl Document {
+    fn validate(mut self) -> darling::Result<Self> {
+        self.data = rename_fields(self.data, &self.options)?;
+        analyzer::check_tokenizers(&all_fields(&self.data), &self.options.analyzers)?;
+        view::check_views(&self.data, &self.options.views)?;
+
+        let mut errors = darling::Error::accumulator();
+        let ids = all_fields(&self.data)
+            .into_iter()
+            .filter(|field| field.id.is_present());
+
+        if self.data.is_enum() && stored_marker(&self.generics, all_fields(&self.data)).is_some() {
+            errors.push(
+                darling::Error::custom(
+                    "every generic parameter of an enum document must be used by a stored field",
+                )
+                .with_span(&self.generics),
+            );
+        }
+
+        for (i, field) in ids.enumerate() {
+            if self.data.is_enum() {
+                errors.push(
+                    darling::Error::custom("`id` is not supported in enum documents")
+                        .with_span(&field.id.span()),
+                );
+            } else if i > 0 {
+                errors.push(
+                    darling::Error::custom("only one field can be marked as `id`")
+                        .with_span(&field.id.span()),
+                );
+            }
+        }
+
+        errors.finish_with(self)
+    }
+
+    /// Returns the implementation of `Identifiable` if one of the fields is marked as `id`.
+    fn parse_id(&self) -> TokenStream {
+        let ast::Data::Struct(ref fields) = self.data else {
+            return TokenStream::new();
+        };
+        let Some(position) = fields.iter().position(|field| field.id.is_present()) else {
+            return TokenStream::new();
+        };
+
+        let generics = document_generics(&self.generics, &self.data);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let name = &self.ident;
+        let Field { ident, ty, .. } = &fields.fields[position];
+        let preceding = fields
+            .iter()
+            .take(position)
+            .filter(|field| !field.skip.is_present())
+            .map(Field::parse_count);
+
+        quote! {
+            impl #impl_generics tantivy_derive::Identifiable for #name #ty_generics #where_clause {
+                type Id = #ty;
+
+                fn id(&self) -> &Self::Id {
+                    &self.#ident
+                }
+
+                fn id_field_id() -> u32 {
+                    use tantivy_derive::Field as _;
+
+                    0 #(
+                        + #preceding
+                    )*
+                }
+            }
+        }
+    }
 }
 
 impl ToTokens for Document {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let generics = document_generics(&self.generics, &self.data);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let name = &self.ident;
-        let stored_name = format_ident!("Stored{name}");
+        let stored_name = self
+            .stored_name
+            .clone()
+            .unwrap_or_else(|| format_ident!("Stored{name}"));
+
+        let (schema_body, count_body, into_body, from_body) = match self.data {
+            ast::Data::Struct(ref fields) => {
+                let mut schema_tokens = Vec::with_capacity(fields.len());
+                let mut count_tokens = Vec::with_capacity(fields.len());
+                let mut from_tokens = Vec::with_capacity(fields.len());
+                let mut field_tokens = Vec::with_capacity(fields.len());
+                let mut into_tokens = Vec::with_capacity(fields.len());
+
+                for field in fields.iter() {
+                    let (schema_token, count_token, from_token, field_token, into_token) =
+                        field.parse(self.options.separator());
+
+                    schema_tokens.push(schema_token);
+                    count_tokens.push(count_token);
+                    from_tokens.push(from_token);
+                    field_tokens.push(field_token);
+                    into_tokens.push(into_token);
+                }
 
-        let fields = self
-            .data
-            .as_ref()
-            .take_struct()
-            .expect("must be struct")
-            .fields;
+                if stored_marker(&self.generics, fields.iter()).is_some() {
+                    field_tokens.push(quote! { _marker: std::marker::PhantomData, });
+                }
 
-        let mut schema_tokens = Vec::with_capacity(fields.len());
-        let mut count_tokens = Vec::with_capacity(fields.len());
-        let mut from_tokens = Vec::with_capacity(fields.len());
-        let mut field_tokens = Vec::with_capacity(fields.len());
-        let mut into_tokens = Vec::with_capacity(fields.len());
+                (
+                    quote! {
+                        #(
+                            #schema_tokens
+                        )*
+                    },
+                    quote! {
+                        #(
+                            #count_tokens
+                        )*
+                    },
+                    quote! {
+                        #(
+                            #into_tokens
+                        )*
+                    },
+                    quote! {
+                        #(
+                            #from_tokens
+                        )*
 
-        for field in fields {
-            let (schema_token, count_token, from_token, field_token, into_token) = field.parse();
+                        Ok(Self::Target {
+                            #(
+                                #field_tokens
+                            )*
+                        })
+                    },
+                )
+            }
+            ast::Data::Enum(ref variants) => {
+                variant::parse_document(variants, &stored_name, &self.options)
+            }
+        };
 
-            schema_tokens.push(schema_token);
-            count_tokens.push(count_token);
-            from_tokens.push(from_token);
-            field_tokens.push(field_token);
-            into_tokens.push(into_token);
-        }
+        let fields = all_fields(&self.data);
+        let search_tokens: Vec<TokenStream> = fields
+            .iter()
+            .map(|field| field.parse_search(self.options.separator()))
+            .collect();
+        let types = fields
+            .iter()
+            .filter(|field| field.with.is_none())
+            .map(|field| field.field_ty());
+        let analyzers = &self.options.analyzers;
+
+        let id_tokens = self.parse_id();
+        let conjunction_by_default = if self.options.conjunction_by_default.is_present() {
+            quote! { const CONJUNCTION_BY_DEFAULT: bool = true; }
+        } else {
+            TokenStream::new()
+        };
+        let view_tokens: Vec<TokenStream> = match self.data {
+            ast::Data::Struct(ref fields) => self
+                .options
+                .views
+                .iter()
+                .map(|view| view.parse_impl(name, &generics, fields))
+                .collect(),
+            ast::Data::Enum(_) => vec![],
+        };
+        let separator = self.options.separator();
+        let version = match self.options.version {
+            Some(version) => quote! { const VERSION: u32 = #version; },
+            None => TokenStream::new(),
+        };
 
         tokens.extend(quote! {
             impl #impl_generics tantivy_derive::Field for #name #ty_generics #where_clause {
-                type Target = #stored_name;
+                type Target = #stored_name #ty_generics;
+
+                const SEPARATOR: &'static str = #separator;
 
                 fn add_field(builder: &mut tantivy::schema::SchemaBuilder, name: &str, options: tantivy_derive::FieldOptions) {
                     use tantivy::schema::*;
                     use tantivy_derive::Field as _;
 
-                    #(
-                        #schema_tokens
-                    )*
+                    #schema_body
                 }
 
                 fn count_fields() -> u32 {
                     let mut count = 0;
 
-                    #(
-                        #count_tokens
-                    )*
+                    #count_body
 
                     count
                 }
 
                 fn insert_into_document(
                     document: &mut tantivy::schema::TantivyDocument,
+                    fields: &tantivy_derive::FieldTable,
                     mut field_id: u32,
                     value: &Self,
                 ) {
+                    #into_body
+                }
+
+                fn search_fields(name: &str, fields: &mut Vec<tantivy_derive::SearchField>) {
+                    use tantivy_derive::Field as _;
+
+                    #(
+                        #search_tokens
+                    )*
+                }
+
+                fn add_tokenizers(tokenizers: &tantivy::tokenizer::TokenizerManager) {
+                    use tantivy_derive::Field as _;
+
+                    #(
+                        #analyzers
+                    )*
                     #(
-                        #into_tokens
+                        <#types>::add_tokenizers(tokenizers);
                     )*
                 }
             }
@@ -293,41 +1443,37 @@ impl ToTokens for Document {
             impl #impl_generics tantivy_derive::Extractable for #name #ty_generics #where_clause {
                 fn extract_from_document(
                     document: &tantivy::schema::TantivyDocument,
+                    fields: &tantivy_derive::FieldTable,
                     mut field_id: u32,
-                ) -> Option<Self::Target> {
+                ) -> Result<Self::Target, tantivy_derive::Error> {
                     use tantivy_derive::{Extractable as _, Field as _};
 
-                    #(
-                        #from_tokens
-                    )*
-
-                    Some(Self::Target {
-                        #(
-                            #field_tokens
-                        )*
-                    })
+                    #from_body
                 }
             }
 
-            impl #impl_generics std::convert::From<#name> for tantivy::schema::TantivyDocument #ty_generics #where_clause {
-                fn from(value: #name) -> tantivy::schema::TantivyDocument {
-                    use tantivy_derive::Field as _;
+            impl #impl_generics std::convert::From<#name #ty_generics> for tantivy::schema::TantivyDocument #where_clause {
+                fn from(value: #name #ty_generics) -> tantivy::schema::TantivyDocument {
+                    use tantivy_derive::Schema as _;
 
-                    let mut document = tantivy::schema::TantivyDocument::new();
-                    #name::insert_into_document(&mut document, 0, &value);
-                    document
+                    value.to_document(&Default::default())
                 }
             }
 
-            impl #impl_generics std::convert::From<tantivy::schema::TantivyDocument> for #stored_name #ty_generics #where_clause {
-                fn from(document: tantivy::schema::TantivyDocument) -> Self {
-                    use tantivy_derive::{Extractable as _, Field as _};
+            impl #impl_generics std::convert::TryFrom<tantivy::schema::TantivyDocument> for #stored_name #ty_generics #where_clause {
+                type Error = tantivy_derive::Error;
+
+                fn try_from(document: tantivy::schema::TantivyDocument) -> Result<Self, Self::Error> {
+                    use tantivy_derive::Schema as _;
 
-                    #name::extract_from_document(&document, 0).expect("missing field")
+                    <#name #ty_generics>::from_document(&document, &Default::default())
                 }
             }
 
             impl #impl_generics tantivy_derive::Schema for #name #ty_generics #where_clause {
+                #conjunction_by_default
+                #version
+
                 fn schema() -> tantivy::schema::Schema {
                     use tantivy::schema::*;
                     use tantivy_derive::Field as _;
@@ -337,6 +1483,12 @@ impl ToTokens for Document {
                     builder.build()
                 }
             }
+
+            #id_tokens
+
+            #(
+                #view_tokens
+            )*
         });
     }
 }
@@ -344,56 +1496,336 @@ impl ToTokens for Document {
 #[derive(Debug, FromDeriveInput)]
 #[darling(
     attributes(tantivy),
-    supports(struct_named),
-    forward_attrs(allow, cfg, derive)
+    supports(struct_named, enum_named, enum_unit),
+    forward_attrs(allow, cfg, derive),
+    and_then = Self::rename_fields
 )]
 struct StoredDocument {
     ident: Ident,
+    #[darling(skip)]
+    document: Option<Ident>,
     vis: syn::Visibility,
-    data: ast::Data<util::Ignored, Field>,
+    generics: syn::Generics,
+    data: ast::Data<Variant, Field>,
     attrs: Vec<syn::Attribute>,
+    #[darling(flatten)]
+    options: ContainerOptions,
+}
+
+impl StoredDocument {
+    fn rename_fields(mut self) -> darling::Result<Self> {
+        self.data = rename_fields(self.data, &self.options)?;
+        Ok(self)
+    }
 }
 
 impl ToTokens for StoredDocument {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = &self.ident;
         let vis = &self.vis;
+        let attrs: Vec<TokenStream> = self.attrs.iter().map(|attr| quote! { #attr }).collect();
+        let generics = stored_generics(&self.generics, all_fields(&self.data));
+        let where_clause = &generics.where_clause;
 
-        let fields = self
-            .data
-            .as_ref()
-            .take_struct()
-            .expect("must be struct")
-            .fields;
+        let fields = match self.data {
+            ast::Data::Struct(ref fields) => fields,
+            ast::Data::Enum(ref variants) => {
+                let variant_tokens = variants.iter().map(Variant::parse_stored);
+
+                tokens.extend(quote! {
+                    #(
+                        #attrs
+                    )*
+                    #vis enum #name #generics #where_clause {
+                        #(
+                            #variant_tokens
+                        )*
+                    }
+                });
+
+                return;
+            }
+        };
 
         let mut field_tokens = Vec::with_capacity(fields.len());
 
-        for field in fields {
+        for field in fields.iter() {
             let token = field.parse_stored();
 
             field_tokens.push(token);
         }
 
-        let attrs: Vec<TokenStream> = self.attrs.iter().map(|attr| quote! { #attr }).collect();
+        if let Some(marker) = stored_marker(&self.generics, fields.iter()) {
+            field_tokens.push(quote! {
+                #[doc(hidden)]
+                #vis _marker: #marker,
+            });
+        }
+
+        let view_tokens = self.options.views.iter().map(|view| {
+            let document = self.document.as_ref().expect("document must be set");
+            view.parse_struct(document, vis, &self.generics, &self.attrs, fields)
+        });
 
         tokens.extend(quote! {
             #(
                 #attrs
             )*
-            #vis struct #name {
+            #vis struct #name #generics #where_clause {
+                #(
+                    #field_tokens
+                )*
+            }
+
+            #(
+                #view_tokens
+            )*
+        });
+    }
+}
+
+/// Returns the entry of a member of the handle struct in its `Debug` output.
+fn parse_debug(member: &Ident) -> TokenStream {
+    let name = member.unraw().to_string();
+
+    quote! {
+        .field(#name, &self.#member)
+    }
+}
+
+/// Returns the member of the handle struct for a field, the expression that resolves it, the
+/// method of the query struct that queries it and the entry of the member in the `Debug` output.
+fn parse_handle(
+    vis: &Visibility,
+    member: &Ident,
+    ty: &Type,
+    path: &TokenStream,
+) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+    let field_token = quote! {
+        #vis #member: <#ty as tantivy_derive::Resolvable>::Handle,
+    };
+
+    let resolve_token = quote! {
+        #member: <#ty as tantivy_derive::Resolvable>::resolve(schema, &#path)?,
+    };
+
+    let query_token = quote! {
+        #vis fn #member(&self) -> <#ty as tantivy_derive::Queryable>::Query {
+            <#ty as tantivy_derive::Queryable>::query(&self.index, self.fields.#member)
+        }
+    };
+
+    (field_token, resolve_token, query_token, parse_debug(member))
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(tantivy),
+    supports(struct_named, enum_named, enum_unit),
+    and_then = Self::rename_fields
+)]
+struct DocumentFields {
+    ident: Ident,
+    #[darling(skip)]
+    document: Option<Ident>,
+    #[darling(skip)]
+    query: Option<Ident>,
+    vis: syn::Visibility,
+    generics: syn::Generics,
+    data: ast::Data<Variant, Field>,
+    #[darling(flatten)]
+    options: ContainerOptions,
+}
+
+impl DocumentFields {
+    fn rename_fields(mut self) -> darling::Result<Self> {
+        self.data = rename_fields(self.data, &self.options)?;
+        Ok(self)
+    }
+
+    /// Returns the generics of the implementations for the document with the given bound on the
+    /// types of the fields that have a handle and refer to a generic parameter.
+    fn bounded_generics(&self, bound: &TokenStream) -> syn::Generics {
+        generics::with_bounds(
+            &document_generics(&self.generics, &self.data),
+            all_fields(&self.data)
+                .into_iter()
+                .filter(|field| !field.skip.is_present() && field.with.is_none())
+                .map(|field| (field.field_ty(), bound)),
+        )
+    }
+}
+
+impl ToTokens for DocumentFields {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.ident;
+        let vis = &self.vis;
+
+        let handles = match self.data {
+            ast::Data::Struct(ref fields) => fields
+                .iter()
+                .filter(|field| !field.skip.is_present())
+                .map(|field| field.parse_handle(self.options.separator()))
+                .collect(),
+            ast::Data::Enum(ref variants) => variant::parse_handles(variants, vis, &self.options),
+        };
+
+        let mut field_tokens = Vec::with_capacity(handles.len());
+        let mut resolve_tokens = Vec::with_capacity(handles.len());
+        let mut query_tokens = Vec::with_capacity(handles.len());
+        let mut debug_tokens = Vec::with_capacity(handles.len());
+
+        for (field_token, resolve_token, query_token, debug_token) in handles {
+            field_tokens.push(field_token);
+            resolve_tokens.push(resolve_token);
+            query_tokens.push(query_token);
+            debug_tokens.push(debug_token);
+        }
+
+        let handle_types: Vec<Type> = all_fields(&self.data)
+            .into_iter()
+            .filter(|field| !field.skip.is_present() && field.with.is_none())
+            .map(Field::field_ty)
+            .collect();
+
+        if let Some(marker) = generics::marker(&self.generics, &handle_types) {
+            field_tokens.push(quote! {
+                #[doc(hidden)]
+                _marker: #marker,
+            });
+            resolve_tokens.push(quote! {
+                _marker: std::marker::PhantomData,
+            });
+        }
+
+        let document = self.document.as_ref().expect("document must be set");
+        let query = self.query.as_ref().expect("query must be set");
+        let name_str = name.to_string();
+
+        let resolvable = self.bounded_generics(&quote! { tantivy_derive::Resolvable });
+        let (impl_generics, ty_generics, where_clause) = resolvable.split_for_impl();
+        let queryable = self.bounded_generics(&quote! { tantivy_derive::Queryable });
+        let (query_impl_generics, _, query_where_clause) = queryable.split_for_impl();
+
+        // The implementations are not derived, since that would require the generic parameters
+        // rather than their handles to implement the traits.
+        tokens.extend(quote! {
+            #vis struct #name #resolvable #where_clause {
                 #(
                     #field_tokens
                 )*
             }
+
+            impl #impl_generics std::clone::Clone for #name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl #impl_generics std::marker::Copy for #name #ty_generics #where_clause {}
+
+            impl #impl_generics std::fmt::Debug for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.debug_struct(#name_str)
+                        #(
+                            #debug_tokens
+                        )*
+                        .finish()
+                }
+            }
+
+            impl #impl_generics #name #ty_generics #where_clause {
+                #vis fn from_schema(schema: &tantivy::schema::Schema) -> tantivy::Result<Self> {
+                    <#document #ty_generics as tantivy_derive::Resolvable>::resolve(schema, "")
+                }
+            }
+
+            impl #impl_generics tantivy_derive::Resolvable for #document #ty_generics #where_clause {
+                type Handle = #name #ty_generics;
+
+                fn resolve(schema: &tantivy::schema::Schema, name: &str) -> tantivy::Result<Self::Handle> {
+                    Ok(#name {
+                        #(
+                            #resolve_tokens
+                        )*
+                    })
+                }
+            }
+
+            #vis struct #query #queryable #query_where_clause {
+                index: tantivy::Index,
+                fields: #name #ty_generics,
+            }
+
+            impl #query_impl_generics std::clone::Clone for #query #ty_generics #query_where_clause {
+                fn clone(&self) -> Self {
+                    Self {
+                        index: self.index.clone(),
+                        fields: self.fields,
+                    }
+                }
+            }
+
+            impl #query_impl_generics #query #ty_generics #query_where_clause {
+                #vis fn new(index: &tantivy::Index) -> tantivy::Result<Self> {
+                    let fields = #name::from_schema(&index.schema())?;
+
+                    Ok(<#document #ty_generics as tantivy_derive::Queryable>::query(index, fields))
+                }
+
+                #(
+                    #query_tokens
+                )*
+            }
+
+            impl #query_impl_generics tantivy_derive::Queryable for #document #ty_generics #query_where_clause {
+                type Query = #query #ty_generics;
+
+                fn query(index: &tantivy::Index, handle: Self::Handle) -> Self::Query {
+                    #query {
+                        index: index.clone(),
+                        fields: handle,
+                    }
+                }
+            }
         });
     }
 }
 
+#[derive(Debug, Default, FromMeta)]
+struct DocumentArgs {
+    #[darling(default)]
+    name: Option<Ident>,
+    #[darling(default)]
+    fields: Option<Ident>,
+    #[darling(default)]
+    query: Option<Ident>,
+}
+
 #[proc_macro_derive(Document, attributes(tantivy))]
 pub fn derive_document(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let receiver = Document::from_derive_input(&input).expect("cannot parse");
-    quote!(#receiver).into()
+
+    match Document::from_derive_input(&input) {
+        Ok(receiver) => quote!(#receiver).into(),
+        Err(e) => e.write_errors().into(),
+    }
+}
+
+#[proc_macro_derive(Field, attributes(tantivy))]
+pub fn derive_field(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let receiver = if let syn::Data::Struct(_) = input.data {
+        field::FieldStruct::from_derive_input(&input).map(|receiver| quote!(#receiver))
+    } else {
+        field::FieldEnum::from_derive_input(&input).map(|receiver| quote!(#receiver))
+    };
+
+    match receiver {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.write_errors().into(),
+    }
 }
 
 #[proc_macro_attribute]
@@ -401,47 +1833,94 @@ pub fn tantivy_document(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    use syn::{Meta, punctuated::Punctuated};
-    let args = parse_macro_input!(args with Punctuated::<Meta, syn::Token![,]>::parse_terminated);
+    let args = match darling::ast::NestedMeta::parse_meta_list(args.into()) {
+        Ok(args) => args,
+        Err(e) => return darling::Error::from(e).write_errors().into(),
+    };
+    let args = match DocumentArgs::from_list(&args) {
+        Ok(args) => args,
+        Err(e) => return e.write_errors().into(),
+    };
     let mut input = parse_macro_input!(input as DeriveInput);
-    let mut struct_name = format_ident!("Stored{}", input.ident);
 
-    for arg in &args {
-        let Ok(arg) = arg.require_name_value() else {
-            continue;
-        };
-        let Some(ident) = arg.path.get_ident() else {
-            continue;
-        };
-        let name = ident.to_string();
+    let struct_name = args
+        .name
+        .unwrap_or_else(|| format_ident!("Stored{}", input.ident));
+    let fields_name = args
+        .fields
+        .unwrap_or_else(|| format_ident!("{}Fields", input.ident));
+    let query_name = args
+        .query
+        .unwrap_or_else(|| format_ident!("{}Query", input.ident));
+
+    let mut original = input.clone();
+    let derived = Document::from_derive_input(&input).map(|mut derived| {
+        derived.stored_name = Some(struct_name.clone());
+        derived
+    });
+
+    let document = std::mem::replace(&mut input.ident, fields_name);
+    let fields = DocumentFields::from_derive_input(&input).map(|mut fields| {
+        fields.document = Some(document.clone());
+        fields.query = Some(query_name);
+        fields
+    });
 
-        if name != "name" {
-            continue;
+    input.ident = struct_name;
+    let receiver = StoredDocument::from_derive_input(&input).map(|mut receiver| {
+        receiver.document = Some(document);
+        receiver
+    });
+
+    // The derive macro reports any errors in the attributes, so only emit the original struct to
+    // avoid reporting the same errors twice or errors about the missing trait implementations.
+    let (Ok(derived), Ok(receiver), Ok(fields)) = (derived, receiver, fields) else {
+        return quote! {
+            #[derive(tantivy_derive::Document)]
+            #original
         }
+        .into();
+    };
 
-        let syn::Expr::Lit(ref value) = arg.value else {
-            continue;
-        };
-        let syn::Lit::Str(ref value) = value.lit else {
-            continue;
-        };
-
-        struct_name = format_ident!("{}", value.value());
-    }
-
-    let original = quote! { #input };
-
-    input.ident = struct_name;
-    let receiver = StoredDocument::from_derive_input(&input).expect("cannot parse");
+    // The document is implemented here rather than by the derive macro, which has no way to learn
+    // the name of the stored struct, so the `tantivy` attributes have to be removed.
+    strip_attributes(&mut original);
 
     quote! {
-        #[derive(tantivy_derive::Document)]
         #original
+        #derived
         #receiver
+        #fields
     }
     .into()
 }
 
+/// Removes the `tantivy` attributes from the struct or enum and its fields and variants, which
+/// are only allowed on items that derive `Document`.
+fn strip_attributes(input: &mut DeriveInput) {
+    let retain = |attrs: &mut Vec<syn::Attribute>| {
+        attrs.retain(|attr| !attr.path().is_ident("tantivy"));
+    };
+
+    retain(&mut input.attrs);
+
+    match input.data {
+        syn::Data::Struct(ref mut data) => data.fields.iter_mut().for_each(|field| {
+            retain(&mut field.attrs);
+        }),
+        syn::Data::Enum(ref mut data) => {
+            for variant in data.variants.iter_mut() {
+                retain(&mut variant.attrs);
+                variant
+                    .fields
+                    .iter_mut()
+                    .for_each(|field| retain(&mut field.attrs));
+            }
+        }
+        syn::Data::Union(_) => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -460,6 +1939,10 @@ mod tests {
         let receiver = StoredDocument::from_derive_input(&parsed).unwrap();
         let tokens = quote::quote!(#receiver);
 
-        panic!("{}", tokens.to_string());
+        assert_eq!(
+            tokens.to_string(),
+            "# [derive (Debug)] pub struct Document { \
+             pub title : < String as tantivy_derive :: Field > :: Target , }",
+        );
     }
 }
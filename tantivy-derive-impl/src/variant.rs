@@ -0,0 +1,320 @@
+use crate::{ContainerOptions, Field, RenameRule, join_path, parse_handle, rename_struct_fields};
+use darling::util::SpannedValue;
+use darling::{FromVariant, ast};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::Ident;
+use syn::ext::IdentExt as _;
+
+/// A variant of an enum document, where every variant adds its own fields to the schema.
+#[derive(Debug, FromVariant)]
+#[darling(attributes(tantivy))]
+pub(crate) struct Variant {
+    ident: Ident,
+    pub(crate) fields: ast::Fields<Field>,
+    #[darling(default)]
+    rename: Option<SpannedValue<String>>,
+    #[darling(default)]
+    rename_all: Option<RenameRule>,
+}
+
+impl Variant {
+    /// Returns the name of the variant as stored in the tag field.
+    fn name(&self) -> String {
+        match self.rename {
+            Some(ref rename) => rename.to_string(),
+            None => self.ident.to_string(),
+        }
+    }
+
+    /// Returns the identifiers of the fields of the variant that are not skipped.
+    fn bindings(&self) -> Vec<&Ident> {
+        self.fields
+            .iter()
+            .filter(|field| !field.skip.is_present())
+            .map(|field| field.ident.as_ref().expect("must be a named variant"))
+            .collect()
+    }
+
+    /// Returns the statements that skip over the fields of the variant.
+    fn skip(&self) -> Vec<TokenStream> {
+        self.fields
+            .iter()
+            .filter(|field| !field.skip.is_present())
+            .map(|field| {
+                let count = field.parse_count();
+
+                quote! {
+                    field_id += #count;
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn parse_stored(&self) -> TokenStream {
+        let ident = &self.ident;
+
+        if self.fields.is_unit() {
+            return quote! { #ident, };
+        }
+
+        let field_tokens = self.fields.iter().map(Field::parse_stored);
+
+        quote! {
+            #ident {
+                #(
+                    #field_tokens
+                )*
+            },
+        }
+    }
+}
+
+/// Applies the `rename_all` rule of the container to the variants and the `rename_all` rule of
+/// every variant to its fields, and checks that every field in the schema has a unique name.
+pub(crate) fn rename_variants(
+    variants: Vec<Variant>,
+    options: &ContainerOptions,
+) -> darling::Result<Vec<Variant>> {
+    let mut errors = darling::Error::accumulator();
+
+    if variants.is_empty() {
+        errors.push(darling::Error::custom(
+            "enum documents must have at least one variant",
+        ));
+    }
+
+    if let Some(ref tag) = options.tag
+        && tag.is_empty()
+    {
+        errors.push(darling::Error::custom("`tag` cannot be empty").with_span(&tag.span()));
+    }
+
+    let mut variant_names = HashSet::new();
+    let mut field_names = HashSet::from([options.tag().to_string()]);
+
+    let variants: Vec<Variant> = variants
+        .into_iter()
+        .map(|mut variant| {
+            variant.fields = rename_struct_fields(variant.fields, variant.rename_all);
+
+            if variant.rename.is_none()
+                && let Some(rule) = options.rename_all
+            {
+                variant.rename = Some(SpannedValue::new(
+                    rule.apply_to_variant(&variant.ident.to_string()),
+                    variant.ident.span(),
+                ));
+            }
+
+            if !variant_names.insert(variant.name()) {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "variant `{}` is defined more than once",
+                        variant.name()
+                    ))
+                    .with_span(&variant.ident),
+                );
+            }
+
+            for field in variant.fields.iter() {
+                if field.flatten.is_present()
+                    || field.skip.is_present()
+                    || field_names.insert(field.name())
+                {
+                    continue;
+                }
+
+                errors.push(
+                    darling::Error::custom(format!(
+                        "field `{}` is defined more than once",
+                        field.name()
+                    ))
+                    .with_span(&field.ident),
+                );
+            }
+
+            variant
+        })
+        .collect();
+
+    errors.finish_with(variants)
+}
+
+/// Returns the bodies of `add_field`, `count_fields`, `insert_into_document` and
+/// `extract_from_document` for an enum document, which consists of a tag field that stores the
+/// variant followed by the fields of every variant.
+pub(crate) fn parse_document(
+    variants: &[Variant],
+    stored_name: &Ident,
+    options: &ContainerOptions,
+) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+    let separator = options.separator();
+    let tag = options.tag();
+    let tag_path = join_path(tag, separator);
+
+    let mut schema_tokens = vec![];
+    let mut count_tokens = vec![];
+    let mut into_arms = Vec::with_capacity(variants.len());
+    let mut from_arms = Vec::with_capacity(variants.len());
+    let mut skip_tokens = vec![];
+
+    let idents: Vec<&Ident> = variants.iter().map(|variant| &variant.ident).collect();
+    let names: Vec<String> = variants.iter().map(Variant::name).collect();
+
+    for variant in variants {
+        let ident = &variant.ident;
+        let name = variant.name();
+        let bindings = variant.bindings();
+
+        let mut from_tokens = Vec::with_capacity(bindings.len());
+        let mut field_tokens = Vec::with_capacity(bindings.len());
+        let mut into_tokens = Vec::with_capacity(bindings.len());
+
+        for field in variant.fields.iter() {
+            let (schema_token, count_token, from_token, field_token, _) = field.parse(separator);
+            let Field { ident, .. } = field;
+
+            schema_tokens.push(schema_token);
+            count_tokens.push(count_token);
+            from_tokens.push(from_token);
+            field_tokens.push(field_token);
+
+            if field.skip.is_present() {
+                continue;
+            }
+
+            let insert_token = field.parse_insert(quote! { #ident });
+            let count = field.parse_count();
+
+            into_tokens.push(quote! {
+                #insert_token
+                field_id += #count;
+            });
+        }
+
+        into_arms.push(quote! {
+            Self::#ident { #(#bindings,)* .. } => {
+                #(
+                    #skip_tokens
+                )*
+                #(
+                    #into_tokens
+                )*
+            }
+        });
+
+        from_arms.push(quote! {
+            #name => {
+                #(
+                    #skip_tokens
+                )*
+                #(
+                    #from_tokens
+                )*
+
+                Ok(#stored_name::#ident {
+                    #(
+                        #field_tokens
+                    )*
+                })
+            }
+        });
+
+        skip_tokens.extend(variant.skip());
+    }
+
+    let schema_body = quote! {
+        let mut options: tantivy_derive::FieldOptions = Default::default();
+        options.set_stored(true);
+        options.set_string(true);
+        <String>::add_field(builder, &#tag_path, options);
+
+        #(
+            #schema_tokens
+        )*
+    };
+
+    let count_body = quote! {
+        count += 1;
+
+        #(
+            #count_tokens
+        )*
+    };
+
+    let into_body = quote! {
+        if let Some(field) = fields.get(field_id) {
+            let tag = match value {
+                #(
+                    Self::#idents { .. } => #names,
+                )*
+            };
+
+            document.add_text(field, tag);
+        }
+
+        field_id += 1;
+
+        match value {
+            #(
+                #into_arms
+            )*
+        }
+    };
+
+    let from_body = quote! {
+        let tag = <String>::extract_from_document(document, fields, field_id)
+            .map_err(|e| e.in_field(#tag, #separator))?;
+        field_id += 1;
+
+        match tag.as_str() {
+            #(
+                #from_arms
+            )*
+            _ => Err(tantivy_derive::Error::invalid_value::<Self, _>(format!(
+                "unknown variant `{tag}`"
+            ))
+            .in_field(#tag, #separator)),
+        }
+    };
+
+    (schema_body, count_body, into_body, from_body)
+}
+
+/// Returns the members of the handle struct of an enum document, the expressions that resolve
+/// them, the methods of the query struct and the entries of the members in the `Debug` output,
+/// where the handles of the fields of a variant are prefixed with the name of the variant.
+pub(crate) fn parse_handles(
+    variants: &[Variant],
+    vis: &syn::Visibility,
+    options: &ContainerOptions,
+) -> Vec<(TokenStream, TokenStream, TokenStream, TokenStream)> {
+    let separator = options.separator();
+    let tag: syn::Type = syn::parse_quote!(String);
+
+    let mut handles = vec![parse_handle(
+        vis,
+        &format_ident!("tag"),
+        &tag,
+        &join_path(options.tag(), separator),
+    )];
+
+    for variant in variants {
+        let prefix = RenameRule::Snake.apply_to_variant(&variant.ident.unraw().to_string());
+
+        for field in variant
+            .fields
+            .iter()
+            .filter(|field| !field.skip.is_present())
+        {
+            let ident = field.ident.as_ref().expect("must be a named variant");
+            let member = format_ident!("{}_{}", prefix, ident.unraw());
+
+            handles.push(field.parse_handle_as(vis, &member, separator));
+        }
+    }
+
+    handles
+}
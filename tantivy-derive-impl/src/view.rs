@@ -0,0 +1,201 @@
+use crate::{Field, Variant, stored_generics, stored_marker};
+use darling::util::PathList;
+use darling::{FromMeta, ast};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::Ident;
+
+/// A projection of the stored fields of a document that is declared with
+/// `#[tantivy(view(name = "...", fields(...)))]`, which generates a struct named after the
+/// document followed by the name of the view.
+#[derive(Clone, Debug, FromMeta)]
+pub(crate) struct View {
+    name: Ident,
+    fields: PathList,
+}
+
+impl View {
+    /// Returns the name of the struct of the view.
+    pub(crate) fn ident(&self, document: &Ident) -> Ident {
+        format_ident!("{}{}", document, self.name)
+    }
+
+    /// Returns whether the field is part of the view.
+    fn contains(&self, field: &Field) -> bool {
+        self.fields.iter().any(|path| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| path.is_ident(ident))
+        })
+    }
+
+    /// Returns the struct of the view, which has the same attributes as the stored document.
+    pub(crate) fn parse_struct(
+        &self,
+        document: &Ident,
+        vis: &syn::Visibility,
+        generics: &syn::Generics,
+        attrs: &[syn::Attribute],
+        fields: &ast::Fields<Field>,
+    ) -> TokenStream {
+        let ident = self.ident(document);
+        let fields: Vec<&Field> = fields.iter().filter(|field| self.contains(field)).collect();
+        let mut field_tokens: Vec<TokenStream> =
+            fields.iter().map(|field| field.parse_stored()).collect();
+
+        if let Some(marker) = stored_marker(generics, fields.iter().copied()) {
+            field_tokens.push(quote! {
+                #[doc(hidden)]
+                #vis _marker: #marker,
+            });
+        }
+
+        let generics = stored_generics(generics, fields.iter().copied());
+        let where_clause = &generics.where_clause;
+
+        quote! {
+            #(
+                #attrs
+            )*
+            #vis struct #ident #generics #where_clause {
+                #(
+                    #field_tokens
+                )*
+            }
+        }
+    }
+
+    /// Returns the implementations of `View` and `TryFrom<TantivyDocument>` for the struct of the
+    /// view, which extract the fields of the view and skip over the other fields.
+    pub(crate) fn parse_impl(
+        &self,
+        document: &Ident,
+        generics: &syn::Generics,
+        fields: &ast::Fields<Field>,
+    ) -> TokenStream {
+        let ident = self.ident(document);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let mut from_tokens = Vec::with_capacity(fields.len());
+        let mut field_tokens = Vec::with_capacity(fields.len());
+
+        for field in fields.iter().filter(|field| !field.skip.is_present()) {
+            let member = &field.ident;
+            let count = field.parse_count();
+
+            if self.contains(field) {
+                from_tokens.push(field.parse_extract());
+                field_tokens.push(quote! { #member, });
+            }
+
+            from_tokens.push(quote! {
+                field_id += #count;
+            });
+        }
+
+        if stored_marker(generics, fields.iter().filter(|field| self.contains(field))).is_some() {
+            field_tokens.push(quote! { _marker: std::marker::PhantomData, });
+        }
+
+        quote! {
+            impl #impl_generics tantivy_derive::View for #ident #ty_generics #where_clause {
+                type Document = #document #ty_generics;
+
+                fn from_document(
+                    document: &tantivy::schema::TantivyDocument,
+                    fields: &tantivy_derive::FieldTable,
+                ) -> Result<Self, tantivy_derive::Error> {
+                    use tantivy_derive::{Extractable as _, Field as _};
+
+                    let mut field_id = 0;
+
+                    #(
+                        #from_tokens
+                    )*
+
+                    Ok(Self {
+                        #(
+                            #field_tokens
+                        )*
+                    })
+                }
+            }
+
+            impl #impl_generics std::convert::TryFrom<tantivy::schema::TantivyDocument> for #ident #ty_generics #where_clause {
+                type Error = tantivy_derive::Error;
+
+                fn try_from(document: tantivy::schema::TantivyDocument) -> Result<Self, Self::Error> {
+                    use tantivy_derive::View as _;
+
+                    Self::from_document(&document, &Default::default())
+                }
+            }
+        }
+    }
+}
+
+/// Checks that the views have unique names and that every field of a view is a stored field of
+/// the document.
+pub(crate) fn check_views(data: &ast::Data<Variant, Field>, views: &[View]) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+
+    let ast::Data::Struct(ref fields) = *data else {
+        if let Some(view) = views.first() {
+            errors.push(
+                darling::Error::custom("views are not supported on enum documents")
+                    .with_span(&view.name),
+            );
+        }
+
+        return errors.finish();
+    };
+
+    let mut names = HashSet::new();
+
+    for view in views {
+        if !names.insert(view.name.to_string()) {
+            errors.push(
+                darling::Error::custom(format!("view `{}` is declared more than once", view.name))
+                    .with_span(&view.name),
+            );
+        }
+
+        if view.fields.is_empty() {
+            errors.push(
+                darling::Error::custom(format!(
+                    "view `{}` must have at least one field",
+                    view.name
+                ))
+                .with_span(&view.name),
+            );
+        }
+
+        for path in view.fields.iter() {
+            let field = fields.iter().find(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| path.is_ident(ident))
+            });
+
+            match field {
+                Some(field) if field.stored.is_present() => {}
+                Some(_) => errors.push(
+                    darling::Error::custom(format!(
+                        "field `{}` must be `stored` to be part of a view",
+                        quote!(#path)
+                    ))
+                    .with_span(path),
+                ),
+                None => errors.push(
+                    darling::Error::custom(format!("unknown field `{}`", quote!(#path)))
+                        .with_span(path),
+                ),
+            }
+        }
+    }
+
+    errors.finish()
+}
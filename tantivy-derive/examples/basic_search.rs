@@ -1,5 +1,4 @@
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
 use tantivy::{Index, IndexWriter, ReloadPolicy, TantivyDocument};
 use tantivy_derive::{Schema, tantivy_document};
 use tempfile::TempDir;
@@ -7,13 +6,13 @@ use tempfile::TempDir;
 #[tantivy_document]
 #[derive(Debug)]
 pub struct Document {
-    #[tantivy(text)]
+    #[tantivy(text, search_default)]
     pub body: String,
-    #[tantivy(stored, text)]
+    #[tantivy(stored, text, search_default, boost = 2.0)]
     pub _title: String,
 }
 
-fn main() -> tantivy::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let index_path = TempDir::new()?;
 
     let schema = Document::schema();
@@ -22,9 +21,6 @@ fn main() -> tantivy::Result<()> {
 
     let mut index_writer: IndexWriter = index.writer(50_000_000)?;
 
-    let title = schema.get_field("title").unwrap();
-    let body = schema.get_field("body").unwrap();
-
     let document = Document {
         _title: "The Old Man and the Sea".to_string(),
         body: "He was an old man who fished alone in a skiff in the Gulf Stream and he had gone \
@@ -58,7 +54,7 @@ fn main() -> tantivy::Result<()> {
 
     let searcher = reader.searcher();
 
-    let query_parser = QueryParser::for_index(&index, vec![title, body]);
+    let query_parser = Document::query_parser(&index);
 
     let query = query_parser.parse_query("sea whale")?;
 
@@ -66,7 +62,7 @@ fn main() -> tantivy::Result<()> {
 
     for (_score, doc_address) in top_docs {
         let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
-        let document: StoredDocument = retrieved_doc.into();
+        let document: StoredDocument = retrieved_doc.try_into()?;
         println!("{document:?}");
     }
 
@@ -0,0 +1,143 @@
+use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tantivy::schema::{OwnedValue, SchemaBuilder, TantivyDocument};
+
+/// Indexes a value as a string through its `Display` and `FromStr` implementations, which is what
+/// `#[tantivy(as_string)]` uses.
+///
+/// The value is retrieved as `T`, where a string that cannot be parsed results in an
+/// [`Error::InvalidValue`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DisplayFromStr<T>(pub T);
+
+impl<T> From<T> for DisplayFromStr<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> DisplayFromStr<T>
+where
+    T: Display + FromStr,
+    T::Err: Display,
+{
+    /// Adds the string representation of the value to the document.
+    pub fn insert_value(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &T,
+    ) {
+        String::insert_into_document(document, fields, field_id, &value.to_string());
+    }
+}
+
+impl<T> Field for DisplayFromStr<T>
+where
+    T: Display + FromStr,
+    T::Err: Display,
+{
+    type Target = T;
+
+    fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+        String::add_field(builder, name, options);
+    }
+
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        Self::insert_value(document, fields, field_id, &value.0);
+    }
+}
+
+impl<T> Mappable for DisplayFromStr<T>
+where
+    T: Display + FromStr,
+    T::Err: Display,
+{
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        String::map_value(value)?
+            .parse()
+            .map_err(Error::invalid_value::<T, _>)
+    }
+}
+
+/// Indexes a value as the representation `R`, such as `u64` or `String`, which is what
+/// `#[tantivy(via = "...")]` uses.
+///
+/// The value is converted into `R` with `From` when it is added to a document, and back with
+/// `TryFrom` when it is retrieved, where a failed conversion results in an
+/// [`Error::InvalidValue`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Via<T, R>(pub T, PhantomData<fn() -> R>);
+
+impl<T, R> Via<T, R> {
+    pub fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+}
+
+impl<T, R> From<T> for Via<T, R> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, R> Via<T, R>
+where
+    T: Clone + TryFrom<R::Target>,
+    T::Error: Display,
+    R: Mappable + From<T>,
+{
+    /// Adds the value to the document as its representation `R`.
+    pub fn insert_value(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &T,
+    ) {
+        R::insert_into_document(document, fields, field_id, &R::from(value.clone()));
+    }
+}
+
+impl<T, R> Field for Via<T, R>
+where
+    T: Clone + TryFrom<R::Target>,
+    T::Error: Display,
+    R: Mappable + From<T>,
+{
+    type Target = T;
+
+    fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+        R::add_field(builder, name, options);
+    }
+
+    fn count_fields() -> u32 {
+        R::count_fields()
+    }
+
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        Self::insert_value(document, fields, field_id, &value.0);
+    }
+}
+
+impl<T, R> Mappable for Via<T, R>
+where
+    T: Clone + TryFrom<R::Target>,
+    T::Error: Display,
+    R: Mappable + From<T>,
+{
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        T::try_from(R::map_value(value)?).map_err(Error::invalid_value::<T, _>)
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+use tantivy::schema::OwnedValue;
+
+/// The errors that can occur when extracting a document from a `TantivyDocument`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// The document does not contain a value for the field.
+    MissingField { field: String },
+    /// The field contains a value of a different type than expected.
+    UnexpectedType {
+        field: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The value of the field could not be converted into the Rust type.
+    InvalidValue {
+        field: String,
+        ty: &'static str,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Creates an error for a missing value.
+    pub fn missing() -> Self {
+        Self::MissingField {
+            field: String::new(),
+        }
+    }
+
+    /// Creates an error for a value that is not of the `expected` type.
+    pub fn unexpected_type(expected: &'static str, value: &OwnedValue) -> Self {
+        Self::UnexpectedType {
+            field: String::new(),
+            expected,
+            found: type_name(value),
+        }
+    }
+
+    /// Creates an error for a value that could not be converted into `T`.
+    pub fn invalid_value<T, M: fmt::Display>(message: M) -> Self {
+        Self::InvalidValue {
+            field: String::new(),
+            ty: std::any::type_name::<T>(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the name of the field that caused the error.
+    pub fn field(&self) -> &str {
+        match self {
+            Self::MissingField { field }
+            | Self::UnexpectedType { field, .. }
+            | Self::InvalidValue { field, .. } => field,
+        }
+    }
+
+    /// Prefixes the name of the field that caused the error with `name`, joined by `separator`.
+    /// This is used to keep track of the field as the error propagates up through nested
+    /// documents, such that the name matches the name of the field in the schema.
+    pub fn in_field(mut self, name: &str, separator: &str) -> Self {
+        let (Self::MissingField { ref mut field }
+        | Self::UnexpectedType { ref mut field, .. }
+        | Self::InvalidValue { ref mut field, .. }) = self;
+
+        *field = if field.is_empty() {
+            name.to_string()
+        } else if name.is_empty() {
+            std::mem::take(field)
+        } else {
+            format!("{name}{separator}{field}")
+        };
+
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField { field } => write!(f, "missing value for field `{field}`"),
+            Self::UnexpectedType {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "unexpected value for field `{field}`: expected {expected}, got {found}"
+            ),
+            Self::InvalidValue { field, ty, message } => write!(
+                f,
+                "invalid value for field `{field}`: cannot convert into `{ty}`: {message}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Returns the name of the type of the value as used in error messages.
+fn type_name(value: &OwnedValue) -> &'static str {
+    match value {
+        OwnedValue::Null => "null",
+        OwnedValue::Str(_) => "str",
+        OwnedValue::PreTokStr(_) => "pre-tokenized str",
+        OwnedValue::U64(_) => "u64",
+        OwnedValue::I64(_) => "i64",
+        OwnedValue::F64(_) => "f64",
+        OwnedValue::Bool(_) => "bool",
+        OwnedValue::Date(_) => "date",
+        OwnedValue::Facet(_) => "facet",
+        OwnedValue::Bytes(_) => "bytes",
+        OwnedValue::Array(_) => "array",
+        OwnedValue::Object(_) => "object",
+        OwnedValue::IpAddr(_) => "ip address",
+    }
+}
@@ -0,0 +1,57 @@
+use crate::query::to_terms;
+use crate::{Field, FieldTable, Schema};
+use tantivy::{IndexWriter, Opstamp, TantivyError, Term};
+
+/// Documents with a field that is marked as `#[tantivy(id)]`, which identifies the document such
+/// that it can be replaced or deleted.
+pub trait Identifiable: Schema {
+    type Id: Field;
+
+    /// Returns the value of the id field.
+    fn id(&self) -> &Self::Id;
+
+    /// Returns the position of the id field in the schema built by [`Schema::schema`].
+    fn id_field_id() -> u32;
+
+    /// Returns the term for the given id using the fields of the given table, or `None` if the
+    /// id field does not exist in the table.
+    fn id_to_term(id: &Self::Id, fields: &FieldTable) -> Option<Term> {
+        let field = fields.get(Self::id_field_id())?;
+
+        to_terms(field, id).into_iter().next()
+    }
+
+    /// Returns the term that identifies the document using the fields of the given table, or an
+    /// error if the id field does not exist in the table.
+    fn id_term(&self, fields: &FieldTable) -> tantivy::Result<Term> {
+        Self::id_to_term(self.id(), fields).ok_or_else(|| {
+            TantivyError::SchemaError("the id field does not exist in the schema".to_string())
+        })
+    }
+
+    /// Deletes the document with the given id, where the fields of the given table must be
+    /// resolved against the schema of the writer, e.g. with [`FieldTable::new`].
+    fn delete(
+        writer: &IndexWriter,
+        fields: &FieldTable,
+        id: &Self::Id,
+    ) -> tantivy::Result<Opstamp> {
+        let term = Self::id_to_term(id, fields).ok_or_else(|| {
+            TantivyError::SchemaError("the id field does not exist in the schema".to_string())
+        })?;
+
+        Ok(writer.delete_term(term))
+    }
+
+    /// Replaces the document with the same id, or adds the document if there is none, where the
+    /// fields of the given table must be resolved against the schema of the writer, e.g. with
+    /// [`FieldTable::new`].
+    fn upsert(
+        writer: &IndexWriter,
+        fields: &FieldTable,
+        document: &Self,
+    ) -> tantivy::Result<Opstamp> {
+        writer.delete_term(document.id_term(fields)?);
+        writer.add_document(document.to_document(fields))
+    }
+}
@@ -0,0 +1,327 @@
+use crate::migrate::{check_version, version_payload};
+use crate::{Error, FieldTable, Identifiable, Schema, SchemaMismatch, View};
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::Path;
+use tantivy::collector::TopDocs;
+use tantivy::query::Query;
+use tantivy::{
+    Index, IndexReader, IndexWriter, Opstamp, ReloadPolicy, Score, TantivyDocument, TantivyError,
+};
+
+/// The memory budget of the writer that [`TypedIndex::add`] creates.
+pub(crate) const DEFAULT_MEMORY_BUDGET: usize = 50_000_000;
+
+/// The errors that can occur when working with a [`TypedIndex`].
+#[derive(Debug)]
+pub enum IndexError {
+    /// The underlying tantivy operation failed.
+    Tantivy(TantivyError),
+    /// A retrieved document could not be converted into the stored document type.
+    Document(Error),
+    /// The index was written with a different version of the schema.
+    Version { expected: u32, found: u32 },
+    /// The schema of the index does not match the schema of the document.
+    Schema(SchemaMismatch),
+}
+
+impl From<TantivyError> for IndexError {
+    fn from(error: TantivyError) -> Self {
+        Self::Tantivy(error)
+    }
+}
+
+impl From<Error> for IndexError {
+    fn from(error: Error) -> Self {
+        Self::Document(error)
+    }
+}
+
+impl From<SchemaMismatch> for IndexError {
+    fn from(mismatch: SchemaMismatch) -> Self {
+        Self::Schema(mismatch)
+    }
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tantivy(error) => write!(f, "{error}"),
+            Self::Document(error) => write!(f, "{error}"),
+            Self::Version { expected, found } => write!(
+                f,
+                "the index has schema version {found}, but version {expected} was expected"
+            ),
+            Self::Schema(mismatch) => write!(f, "{mismatch}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Tantivy(error) => Some(error),
+            Self::Document(error) => Some(error),
+            Self::Version { .. } => None,
+            Self::Schema(mismatch) => Some(mismatch),
+        }
+    }
+}
+
+/// A tantivy index that stores documents of type `D`.
+///
+/// The index takes care of building the schema, registering the analyzers of the document and
+/// converting documents when they are added or retrieved.
+pub struct TypedIndex<D> {
+    index: Index,
+    fields: FieldTable,
+    reader: TypedIndexReader<D>,
+    writer: Option<TypedIndexWriter<D>>,
+}
+
+impl<D: Schema> TypedIndex<D> {
+    /// Creates a new index in the given directory with the schema of `D`.
+    pub fn create_in_dir<P: AsRef<Path>>(path: P) -> Result<Self, IndexError> {
+        Self::from_index(Index::create_in_dir(path, D::schema())?)
+    }
+
+    /// Creates a new index in memory with the schema of `D`.
+    pub fn create_in_ram() -> Result<Self, IndexError> {
+        Self::from_index(Index::create_in_ram(D::schema()))
+    }
+
+    /// Opens an existing index in the given directory, which is checked like
+    /// [`TypedIndex::from_index`].
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, IndexError> {
+        Self::from_index(Index::open_in_dir(path)?)
+    }
+
+    /// Wraps an existing index, resolving the fields of `D` by name against its schema.
+    ///
+    /// Fails with [`IndexError::Schema`] if the schema of the index does not match the schema of
+    /// `D`, and with [`IndexError::Version`] if the index was written with another version of
+    /// `D`.
+    pub fn from_index(index: Index) -> Result<Self, IndexError> {
+        D::verify_schema(&index.schema())?;
+        check_version::<D>(&index)?;
+        D::register_tokenizers(&index);
+
+        let fields = FieldTable::new::<D>(&index.schema());
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+
+        Ok(Self {
+            reader: TypedIndexReader::new(reader, fields.clone()),
+            index,
+            fields,
+            writer: None,
+        })
+    }
+
+    /// Returns the underlying tantivy index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Creates a writer with the given memory budget in bytes.
+    pub fn writer(&self, memory_budget: usize) -> tantivy::Result<TypedIndexWriter<D>> {
+        Ok(TypedIndexWriter::new(
+            self.index.writer(memory_budget)?,
+            self.fields.clone(),
+        ))
+    }
+
+    /// Creates a reader that is reloaded shortly after every commit.
+    pub fn reader(&self) -> tantivy::Result<TypedIndexReader<D>> {
+        let reader = self
+            .index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()?;
+
+        Ok(TypedIndexReader::new(reader, self.fields.clone()))
+    }
+
+    /// Returns the writer used by [`TypedIndex::add`], creating it if necessary.
+    fn default_writer(&mut self) -> tantivy::Result<&TypedIndexWriter<D>> {
+        match self.writer {
+            Some(ref writer) => Ok(writer),
+            None => Ok(self.writer.insert(self.writer(DEFAULT_MEMORY_BUDGET)?)),
+        }
+    }
+
+    /// Adds a document, which becomes visible to [`TypedIndex::search`] after the next
+    /// [`TypedIndex::commit`].
+    pub fn add(&mut self, document: &D) -> tantivy::Result<Opstamp> {
+        self.default_writer()?.add(document)
+    }
+
+    /// Commits the added documents.
+    pub fn commit(&mut self) -> tantivy::Result<Option<Opstamp>> {
+        let Some(ref mut writer) = self.writer else {
+            return Ok(None);
+        };
+
+        writer.commit().map(Some)
+    }
+
+    /// Returns the `limit` best matching documents for the query along with their scores.
+    ///
+    /// The reader is reloaded first, such that the search sees every commit, including those of
+    /// writers created with [`TypedIndex::writer`].
+    pub fn search(
+        &self,
+        query: &dyn Query,
+        limit: usize,
+    ) -> Result<Vec<(Score, D::Target)>, IndexError> {
+        self.reader.reload()?;
+        self.reader.search(query, limit)
+    }
+
+    /// Returns the `limit` best matching documents for the query as the view `V`, reloading the
+    /// reader first like [`TypedIndex::search`].
+    pub fn search_view<V: View<Document = D>>(
+        &self,
+        query: &dyn Query,
+        limit: usize,
+    ) -> Result<Vec<(Score, V)>, IndexError> {
+        self.reader.reload()?;
+        self.reader.search_view(query, limit)
+    }
+}
+
+impl<D: Identifiable> TypedIndex<D> {
+    /// Replaces the document with the same id, or adds the document if there is none.
+    pub fn upsert(&mut self, document: &D) -> tantivy::Result<Opstamp> {
+        self.default_writer()?.upsert(document)
+    }
+
+    /// Deletes the document with the given id.
+    pub fn delete(&mut self, id: &D::Id) -> tantivy::Result<Opstamp> {
+        self.default_writer()?.delete(id)
+    }
+}
+
+/// A writer that adds documents of type `D` to a [`TypedIndex`].
+pub struct TypedIndexWriter<D> {
+    writer: IndexWriter,
+    fields: FieldTable,
+    marker: PhantomData<fn(&D)>,
+}
+
+impl<D: Schema> TypedIndexWriter<D> {
+    pub(crate) fn new(writer: IndexWriter, fields: FieldTable) -> Self {
+        Self {
+            writer,
+            fields,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying tantivy writer.
+    pub fn inner(&self) -> &IndexWriter {
+        &self.writer
+    }
+
+    pub fn add(&self, document: &D) -> tantivy::Result<Opstamp> {
+        self.writer.add_document(document.to_document(&self.fields))
+    }
+
+    /// Commits the added documents and records the version of the schema of `D` in the index.
+    pub fn commit(&mut self) -> tantivy::Result<Opstamp> {
+        let mut commit = self.writer.prepare_commit()?;
+        commit.set_payload(&version_payload::<D>());
+        commit.commit()
+    }
+
+    pub fn rollback(&mut self) -> tantivy::Result<Opstamp> {
+        self.writer.rollback()
+    }
+}
+
+impl<D: Identifiable> TypedIndexWriter<D> {
+    /// Replaces the document with the same id, or adds the document if there is none.
+    pub fn upsert(&self, document: &D) -> tantivy::Result<Opstamp> {
+        D::upsert(&self.writer, &self.fields, document)
+    }
+
+    /// Deletes the document with the given id.
+    pub fn delete(&self, id: &D::Id) -> tantivy::Result<Opstamp> {
+        D::delete(&self.writer, &self.fields, id)
+    }
+}
+
+/// A reader that retrieves documents of type `D` from a [`TypedIndex`].
+pub struct TypedIndexReader<D> {
+    reader: IndexReader,
+    fields: FieldTable,
+    marker: PhantomData<fn() -> D>,
+}
+
+impl<D> Clone for TypedIndexReader<D> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            fields: self.fields.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<D: Schema> TypedIndexReader<D> {
+    fn new(reader: IndexReader, fields: FieldTable) -> Self {
+        Self {
+            reader,
+            fields,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying tantivy reader.
+    pub fn inner(&self) -> &IndexReader {
+        &self.reader
+    }
+
+    pub fn reload(&self) -> tantivy::Result<()> {
+        self.reader.reload()
+    }
+
+    /// Returns the `limit` best matching documents for the query along with their scores.
+    pub fn search(
+        &self,
+        query: &dyn Query,
+        limit: usize,
+    ) -> Result<Vec<(Score, D::Target)>, IndexError> {
+        self.search_with(query, limit, D::from_document)
+    }
+
+    /// Returns the `limit` best matching documents for the query as the view `V`.
+    pub fn search_view<V: View<Document = D>>(
+        &self,
+        query: &dyn Query,
+        limit: usize,
+    ) -> Result<Vec<(Score, V)>, IndexError> {
+        self.search_with(query, limit, V::from_document)
+    }
+
+    fn search_with<T>(
+        &self,
+        query: &dyn Query,
+        limit: usize,
+        extract: fn(&TantivyDocument, &FieldTable) -> Result<T, Error>,
+    ) -> Result<Vec<(Score, T)>, IndexError> {
+        let searcher = self.reader.searcher();
+        let top_docs = searcher.search(query, &TopDocs::with_limit(limit))?;
+
+        top_docs
+            .into_iter()
+            .map(|(score, address)| {
+                let document: TantivyDocument = searcher.doc(address)?;
+
+                Ok((score, extract(&document, &self.fields)?))
+            })
+            .collect()
+    }
+}
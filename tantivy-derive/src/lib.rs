@@ -1,43 +1,224 @@
+mod adapter;
+mod error;
+mod id;
+mod index;
+mod migrate;
 mod options;
+mod query;
+mod search;
+mod table;
+mod verify;
 
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::hash::Hash;
 use std::net::Ipv6Addr;
+use std::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroIsize, NonZeroU8, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroUsize,
+};
 use tantivy::schema::*;
-pub use tantivy_derive_impl::{Document, tantivy_document};
+pub use tantivy_derive_impl::{Document, Field, tantivy_document};
 
+pub use crate::adapter::{DisplayFromStr, Via};
+pub use crate::error::Error;
+pub use crate::id::Identifiable;
+pub use crate::index::{IndexError, TypedIndex, TypedIndexReader, TypedIndexWriter};
+pub use crate::migrate::{index_version, migrate};
 pub use crate::options::FieldOptions;
+pub use crate::query::{FieldQuery, Queryable};
+pub use crate::search::{Fuzzy, SearchField};
+pub use crate::table::FieldTable;
+pub use crate::verify::{OptionChange, SchemaMismatch, TypeChange};
 
 pub trait Field: Sized {
     type Target;
 
+    /// The separator between the name of the field and the names of the fields of a nested
+    /// document, as set with `#[tantivy(separator = "...")]`.
+    const SEPARATOR: &'static str = ".";
+
     fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions);
     fn count_fields() -> u32 {
         1
     }
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    );
+    /// Collects the query parser settings of the fields of a nested document.
+    fn search_fields(_name: &str, _fields: &mut Vec<SearchField>) {}
+    /// Registers the analyzers declared by a document, including those of nested documents.
+    fn add_tokenizers(_tokenizers: &tantivy::tokenizer::TokenizerManager) {}
 }
 
 pub trait Mappable: Field {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target>;
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error>;
+}
+
+/// Extracts the first value of a field from a document and converts it with `map_value`, which is
+/// used by the fields that are converted `with` a module.
+pub fn extract_value<T>(
+    document: &TantivyDocument,
+    fields: &FieldTable,
+    field_id: u32,
+    map_value: fn(&OwnedValue) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let value = fields
+        .get(field_id)
+        .and_then(|field| document.get_first(field))
+        .ok_or_else(Error::missing)?;
+
+    map_value(&value.into())
+}
+
+/// Returns the filter that removes the stop words of the language, which is used by the analyzers
+/// that declare `stopwords`.
+///
+/// The derive macro only accepts languages for which tantivy provides stop words, but the filter
+/// removes nothing for any other language.
+pub fn stop_words(language: tantivy::tokenizer::Language) -> tantivy::tokenizer::StopWordFilter {
+    tantivy::tokenizer::StopWordFilter::new(language)
+        .unwrap_or_else(|| tantivy::tokenizer::StopWordFilter::remove([]))
 }
 
 pub trait Extractable: Field {
-    fn extract_from_document(document: &TantivyDocument, field_id: u32) -> Option<Self::Target>;
+    fn extract_from_document(
+        document: &TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+    ) -> Result<Self::Target, Error>;
 }
 
-pub trait Schema {
+/// Types whose schema fields can be looked up by name, such as the fields of a nested document.
+pub trait Resolvable: Field {
+    type Handle: Copy + std::fmt::Debug;
+
+    fn resolve(schema: &tantivy::schema::Schema, name: &str) -> tantivy::Result<Self::Handle>;
+}
+
+pub trait Schema: Extractable {
+    /// Whether the query parser requires all terms of a query to match.
+    const CONJUNCTION_BY_DEFAULT: bool = false;
+    /// The version of the schema as declared with `#[tantivy(version = N)]`, which is recorded in
+    /// the index whenever a [`TypedIndexWriter`] commits.
+    const VERSION: u32 = 0;
+
     fn schema() -> tantivy::schema::Schema;
+
+    /// Checks that the given schema, e.g. the schema of an existing index, matches the schema of
+    /// the document. Fields are matched by name and compared by type and options.
+    fn verify_schema(schema: &tantivy::schema::Schema) -> Result<(), SchemaMismatch> {
+        let mismatch = SchemaMismatch::between(&Self::schema(), schema);
+
+        if mismatch.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatch)
+        }
+    }
+
+    /// Registers the analyzers declared with `#[tantivy(analyzer(...))]` on the index, both for
+    /// indexing text and for fast fields.
+    fn register_tokenizers(index: &tantivy::Index) {
+        Self::add_tokenizers(index.tokenizers());
+        Self::add_tokenizers(index.fast_field_tokenizer());
+    }
+
+    /// Returns a query parser that searches the fields marked as `search_default`, with the boosts
+    /// and fuzzy settings of the fields applied. Fields that do not exist in the schema of the
+    /// index are skipped.
+    fn query_parser(index: &tantivy::Index) -> tantivy::query::QueryParser {
+        let schema = index.schema();
+        let mut search_fields = vec![];
+        Self::search_fields("", &mut search_fields);
+
+        let search_fields: Vec<_> = search_fields
+            .into_iter()
+            .filter_map(|search_field| {
+                let field = schema.get_field(&search_field.name).ok()?;
+                Some((field, search_field))
+            })
+            .collect();
+
+        let default_fields = search_fields
+            .iter()
+            .filter(|(_, search_field)| search_field.default)
+            .map(|(field, _)| *field)
+            .collect();
+
+        let mut query_parser = tantivy::query::QueryParser::for_index(index, default_fields);
+
+        for (field, search_field) in search_fields {
+            if let Some(boost) = search_field.boost {
+                query_parser.set_field_boost(field, boost);
+            }
+
+            if let Some(fuzzy) = search_field.fuzzy {
+                query_parser.set_field_fuzzy(
+                    field,
+                    fuzzy.prefix,
+                    fuzzy.distance,
+                    fuzzy.transpose_cost_one,
+                );
+            }
+        }
+
+        if Self::CONJUNCTION_BY_DEFAULT {
+            query_parser.set_conjunction_by_default();
+        }
+
+        query_parser
+    }
+
+    /// Converts the document into a `TantivyDocument` using the fields of the given table.
+    fn to_document(&self, fields: &FieldTable) -> TantivyDocument {
+        let mut document = TantivyDocument::new();
+        Self::insert_into_document(&mut document, fields, 0, self);
+        document
+    }
+
+    /// Extracts the stored fields from a `TantivyDocument` using the fields of the given table.
+    fn from_document(
+        document: &TantivyDocument,
+        fields: &FieldTable,
+    ) -> Result<Self::Target, Error> {
+        Self::extract_from_document(document, fields, 0)
+    }
 }
 
-impl<T> Extractable for T
+/// A projection of the stored fields of a document, as declared with
+/// `#[tantivy(view(name = "...", fields(...)))]`.
+pub trait View: Sized {
+    type Document: Schema;
+
+    /// Extracts the fields of the view from a `TantivyDocument` using the fields of the given
+    /// table, which is resolved for the schema of the document.
+    fn from_document(document: &TantivyDocument, fields: &FieldTable) -> Result<Self, Error>;
+}
+
+impl<T> Resolvable for T
 where
     T: Mappable,
 {
-    fn extract_from_document(document: &TantivyDocument, field_id: u32) -> Option<Self::Target> {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    type Handle = tantivy::schema::Field;
 
-        document
-            .get_first(field)
-            .and_then(|v| Self::map_value(&v.into()))
+    fn resolve(schema: &tantivy::schema::Schema, name: &str) -> tantivy::Result<Self::Handle> {
+        schema.get_field(name)
+    }
+}
+
+impl<T> Extractable for T
+where
+    T: Mappable,
+{
+    fn extract_from_document(
+        document: &TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+    ) -> Result<Self::Target, Error> {
+        extract_value(document, fields, field_id, Self::map_value)
     }
 }
 
@@ -49,16 +230,25 @@ impl Field for bool {
         builder.add_bool_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_bool(field, *value);
     }
 }
 
 impl Mappable for bool {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_bool()
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_bool()
+            .ok_or_else(|| Error::unexpected_type("bool", value))
     }
 }
 
@@ -70,16 +260,25 @@ impl Field for u64 {
         builder.add_u64_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_u64(field, *value);
     }
 }
 
 impl Mappable for u64 {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_u64()
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_u64()
+            .ok_or_else(|| Error::unexpected_type("u64", value))
     }
 }
 
@@ -88,19 +287,28 @@ impl Field for i64 {
 
     fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
         let options: NumericOptions = options.into();
-        builder.add_u64_field(name, options);
+        builder.add_i64_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_i64(field, *value);
     }
 }
 
 impl Mappable for i64 {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_i64()
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_i64()
+            .ok_or_else(|| Error::unexpected_type("i64", value))
     }
 }
 
@@ -112,19 +320,158 @@ impl Field for f64 {
         builder.add_f64_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_f64(field, *value);
     }
 }
 
 impl Mappable for f64 {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_f64()
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_f64()
+            .ok_or_else(|| Error::unexpected_type("f64", value))
+    }
+}
+
+/// Implements the traits for numeric types that are widened into the 64-bit numeric type `$wide`
+/// of tantivy, where retrieving a value that does not fit in the type results in an error rather
+/// than being truncated.
+macro_rules! impl_numeric {
+    ($wide:ident, $add_field:ident, $add:ident, $as:ident: $($ty:ty),*) => {
+        $(
+            impl Field for $ty {
+                type Target = Self;
+
+                fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+                    let options: NumericOptions = options.into();
+                    builder.$add_field(name, options);
+                }
+
+                fn insert_into_document(
+                    document: &mut TantivyDocument,
+                    fields: &FieldTable,
+                    field_id: u32,
+                    value: &Self,
+                ) {
+                    let Some(field) = fields.get(field_id) else {
+                        return;
+                    };
+
+                    document.$add(field, *value as $wide);
+                }
+            }
+
+            impl Mappable for $ty {
+                fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+                    let value = value
+                        .$as()
+                        .ok_or_else(|| Error::unexpected_type(stringify!($wide), value))?;
+
+                    <$ty>::try_from(value).map_err(|_| {
+                        Error::invalid_value::<$ty, _>(format!("{value} is out of range"))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(u64, add_u64_field, add_u64, as_u64: u8, u16, u32, usize);
+impl_numeric!(i64, add_i64_field, add_i64, as_i64: i8, i16, i32, isize);
+
+impl Field for f32 {
+    type Target = Self;
+
+    fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+        let options: NumericOptions = options.into();
+        builder.add_f64_field(name, options);
+    }
+
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
+
+        document.add_f64(field, *value as f64);
     }
 }
 
+impl Mappable for f32 {
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        let value = value
+            .as_f64()
+            .ok_or_else(|| Error::unexpected_type("f64", value))?;
+
+        if value.is_finite() && value.abs() > f32::MAX as f64 {
+            return Err(Error::invalid_value::<f32, _>(format!(
+                "{value} is out of range"
+            )));
+        }
+
+        Ok(value as f32)
+    }
+}
+
+/// Implements the traits for the non-zero integer types by forwarding to the underlying integer
+/// type, where retrieving a zero results in an error.
+macro_rules! impl_non_zero {
+    ($($ty:ty => $inner:ty),*) => {
+        $(
+            impl Field for $ty {
+                type Target = Self;
+
+                fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+                    <$inner>::add_field(builder, name, options);
+                }
+
+                fn insert_into_document(
+                    document: &mut TantivyDocument,
+                    fields: &FieldTable,
+                    field_id: u32,
+                    value: &Self,
+                ) {
+                    <$inner>::insert_into_document(document, fields, field_id, &value.get());
+                }
+            }
+
+            impl Mappable for $ty {
+                fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+                    let value = <$inner>::map_value(value)?;
+
+                    <$ty>::new(value).ok_or_else(|| Error::invalid_value::<$ty, _>("value is zero"))
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroIsize => isize
+);
+
 impl Field for String {
     type Target = Self;
 
@@ -133,16 +480,26 @@ impl Field for String {
         builder.add_text_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_text(field, value.as_str());
     }
 }
 
 impl Mappable for String {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_str().map(|s| s.to_string())
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| Error::unexpected_type("str", value))
     }
 }
 
@@ -154,16 +511,27 @@ impl Field for Facet {
         builder.add_facet_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_facet(field, value.clone());
     }
 }
 
 impl Mappable for Facet {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        Some(value.as_facet()?.to_string())
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_facet()
+            .and_then(|facet| Facet::from_encoded(facet.as_bytes().to_vec()).ok())
+            .map(|facet| facet.to_path_string())
+            .ok_or_else(|| Error::unexpected_type("facet", value))
     }
 }
 
@@ -175,16 +543,25 @@ impl Field for Ipv6Addr {
         builder.add_ip_addr_field(name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
+        let Some(field) = fields.get(field_id) else {
+            return;
+        };
 
         document.add_ip_addr(field, *value);
     }
 }
 
 impl Mappable for Ipv6Addr {
-    fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-        value.as_ip_addr()
+    fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+        value
+            .as_ip_addr()
+            .ok_or_else(|| Error::unexpected_type("ip address", value))
     }
 }
 
@@ -195,60 +572,132 @@ impl<T: Mappable> Field for Option<T> {
         T::add_field(builder, name, options);
     }
 
-    fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
+    fn insert_into_document(
+        document: &mut TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+        value: &Self,
+    ) {
         if let Some(value) = value {
-            T::insert_into_document(document, field_id, value);
+            T::insert_into_document(document, fields, field_id, value);
         }
     }
 }
 
 impl<T: Mappable<Target = T>> Extractable for Option<T> {
-    fn extract_from_document(document: &TantivyDocument, field_id: u32) -> Option<Self::Target> {
-        let field = tantivy::schema::Field::from_field_id(field_id);
-
-        Some(
-            document
-                .get_first(field)
-                .and_then(|v| T::map_value(&v.into())),
-        )
+    fn extract_from_document(
+        document: &TantivyDocument,
+        fields: &FieldTable,
+        field_id: u32,
+    ) -> Result<Self::Target, Error> {
+        let Some(value) = fields
+            .get(field_id)
+            .and_then(|field| document.get_first(field))
+        else {
+            return Ok(None);
+        };
+
+        T::map_value(&value.into()).map(Some)
     }
 }
 
-impl<T: Mappable> Field for Vec<T>
-where
-    std::vec::Vec<T>: FromIterator<<T as Field>::Target>,
-{
-    type Target = Self;
+impl<T: Mappable> Resolvable for Option<T> {
+    type Handle = <T as Resolvable>::Handle;
 
-    fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
-        T::add_field(builder, name, options);
+    fn resolve(schema: &tantivy::schema::Schema, name: &str) -> tantivy::Result<Self::Handle> {
+        T::resolve(schema, name)
     }
+}
 
-    fn insert_into_document(document: &mut TantivyDocument, mut field_id: u32, value: &Self) {
-        for value in value {
-            T::insert_into_document(document, field_id, value);
-            field_id += 1;
-        }
+impl<T: Mappable> Queryable for Option<T> {
+    type Query = <T as Queryable>::Query;
+
+    fn query(index: &tantivy::Index, handle: Self::Handle) -> Self::Query {
+        T::query(index, handle)
     }
 }
 
-impl<T: Mappable> Extractable for Vec<T>
-where
-    std::vec::Vec<T>: FromIterator<<T as Field>::Target>,
-{
-    fn extract_from_document(document: &TantivyDocument, field_id: u32) -> Option<Self::Target> {
-        let field = tantivy::schema::Field::from_field_id(field_id);
+/// Implements the traits for a collection of values, which are all added to the same
+/// multi-valued field and retrieved from that field again.
+macro_rules! impl_collection {
+    ($collection:ident $(, $bound:path)*) => {
+        impl<T: Mappable> Field for $collection<T>
+        where
+            <T as Field>::Target: $($bound +)*,
+        {
+            type Target = $collection<<T as Field>::Target>;
+
+            fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+                T::add_field(builder, name, options);
+            }
+
+            fn insert_into_document(
+                document: &mut TantivyDocument,
+                fields: &FieldTable,
+                field_id: u32,
+                value: &Self,
+            ) {
+                for value in value {
+                    T::insert_into_document(document, fields, field_id, value);
+                }
+            }
+        }
 
-        document
-            .get_all(field)
-            .map(|v| T::map_value(&v.into()))
-            .collect()
-    }
+        impl<T: Mappable> Extractable for $collection<T>
+        where
+            <T as Field>::Target: $($bound +)*,
+        {
+            fn extract_from_document(
+                document: &TantivyDocument,
+                fields: &FieldTable,
+                field_id: u32,
+            ) -> Result<Self::Target, Error> {
+                let Some(field) = fields.get(field_id) else {
+                    return Ok(Default::default());
+                };
+
+                document
+                    .get_all(field)
+                    .map(|v| T::map_value(&v.into()))
+                    .collect()
+            }
+        }
+
+        impl<T: Mappable> Resolvable for $collection<T>
+        where
+            <T as Field>::Target: $($bound +)*,
+        {
+            type Handle = <T as Resolvable>::Handle;
+
+            fn resolve(
+                schema: &tantivy::schema::Schema,
+                name: &str,
+            ) -> tantivy::Result<Self::Handle> {
+                T::resolve(schema, name)
+            }
+        }
+
+        impl<T: Mappable> Queryable for $collection<T>
+        where
+            <T as Field>::Target: $($bound +)*,
+        {
+            type Query = <T as Queryable>::Query;
+
+            fn query(index: &tantivy::Index, handle: Self::Handle) -> Self::Query {
+                T::query(index, handle)
+            }
+        }
+    };
 }
 
+impl_collection!(Vec);
+impl_collection!(VecDeque);
+impl_collection!(BTreeSet, Ord);
+impl_collection!(HashSet, Eq, Hash);
+
 #[cfg(feature = "bytes")]
 mod bytes {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use bytes::Bytes;
     use tantivy::schema::*;
 
@@ -260,23 +709,33 @@ mod bytes {
             builder.add_bytes_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             document.add_bytes(field, &value[..]);
         }
     }
 
     impl Mappable for Bytes {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-            value.as_bytes().map(|bytes| Bytes::from(bytes.to_vec()))
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            value
+                .as_bytes()
+                .map(|bytes| Bytes::from(bytes.to_vec()))
+                .ok_or_else(|| Error::unexpected_type("bytes", value))
         }
     }
 }
 
 #[cfg(feature = "chrono")]
 mod chrono {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use chrono::{DateTime, NaiveDate, Utc};
     use tantivy::schema::*;
 
@@ -288,8 +747,15 @@ mod chrono {
             builder.add_date_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             let nanos = value.timestamp_nanos_opt().unwrap_or(0);
             let value = tantivy::DateTime::from_timestamp_nanos(nanos);
@@ -298,10 +764,11 @@ mod chrono {
     }
 
     impl Mappable for DateTime<Utc> {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
             value
                 .as_datetime()
                 .map(|v| DateTime::from_timestamp_nanos(v.into_timestamp_nanos()))
+                .ok_or_else(|| Error::unexpected_type("date", value))
         }
     }
 
@@ -313,8 +780,15 @@ mod chrono {
             builder.add_date_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             let value = value.and_hms_opt(0, 0, 0).unwrap().and_utc();
             let nanos = value.timestamp_nanos_opt().unwrap_or(0);
@@ -324,17 +798,18 @@ mod chrono {
     }
 
     impl Mappable for NaiveDate {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
             value
                 .as_datetime()
                 .map(|v| DateTime::from_timestamp_nanos(v.into_timestamp_nanos()).date_naive())
+                .ok_or_else(|| Error::unexpected_type("date", value))
         }
     }
 }
 
 #[cfg(feature = "decimal")]
 mod decimal {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use rust_decimal::Decimal;
     use tantivy::schema::*;
 
@@ -346,8 +821,15 @@ mod decimal {
             builder.add_bytes_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
             let slice = Decimal::serialize(value);
 
             document.add_bytes(field, &slice);
@@ -355,17 +837,22 @@ mod decimal {
     }
 
     impl Mappable for Decimal {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-            value.as_bytes().and_then(|bytes| {
-                if bytes.len() != 16 {
-                    return None;
-                }
-
-                let mut slice = [0u8; 16];
-                slice.copy_from_slice(&bytes[..16]);
-
-                Some(Decimal::deserialize(slice))
-            })
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            let bytes = value
+                .as_bytes()
+                .ok_or_else(|| Error::unexpected_type("bytes", value))?;
+
+            if bytes.len() != 16 {
+                return Err(Error::invalid_value::<Decimal, _>(format!(
+                    "expected 16 bytes, got {}",
+                    bytes.len()
+                )));
+            }
+
+            let mut slice = [0u8; 16];
+            slice.copy_from_slice(&bytes[..16]);
+
+            Ok(Decimal::deserialize(slice))
         }
     }
 
@@ -385,8 +872,15 @@ mod decimal {
             builder.add_i64_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
             let mut value = value.0;
             value.rescale(N);
             let value = value.mantissa() as i64;
@@ -396,10 +890,11 @@ mod decimal {
     }
 
     impl<const N: u32> Mappable for FixedDecimal<N> {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
             value
                 .as_i64()
                 .map(|value| FixedDecimal(Decimal::from_i128_with_scale(value as i128, N)))
+                .ok_or_else(|| Error::unexpected_type("i64", value))
         }
     }
 }
@@ -409,7 +904,7 @@ pub use decimal::FixedDecimal;
 
 #[cfg(feature = "jiff")]
 mod jiff {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use jiff::Timestamp;
     use tantivy::schema::*;
 
@@ -421,8 +916,15 @@ mod jiff {
             builder.add_date_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             let nanos = value.as_nanosecond() as i64;
             let value = tantivy::DateTime::from_timestamp_nanos(nanos);
@@ -431,17 +933,172 @@ mod jiff {
     }
 
     impl Mappable for Timestamp {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-            value
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            let value = value
                 .as_datetime()
-                .and_then(|v| Timestamp::from_nanosecond(v.into_timestamp_nanos() as i128).ok())
+                .ok_or_else(|| Error::unexpected_type("date", value))?;
+
+            Timestamp::from_nanosecond(value.into_timestamp_nanos() as i128)
+                .map_err(Error::invalid_value::<Timestamp, _>)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+    use std::ops::{Deref, DerefMut};
+    use tantivy::schema::*;
+
+    /// Maps any value that can be serialized into a JSON object onto a JSON field.
+    ///
+    /// A value that cannot be serialized into JSON, e.g. a map with keys that are not strings,
+    /// cannot be indexed. Inserting such a value panics in debug builds and leaves the field out
+    /// of the document in release builds, such that retrieving it fails with a missing field
+    /// error. Use [`Json::try_new`] to find out whether a value can be serialized before it is
+    /// added to a document.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub struct Json<T>(pub T);
+
+    impl<T: Serialize> Json<T> {
+        /// Wraps the value if it can be serialized into JSON, and returns the serialization error
+        /// otherwise.
+        pub fn try_new(value: T) -> Result<Self, serde_json::Error> {
+            serde_json::to_value(&value)?;
+
+            Ok(Self(value))
+        }
+    }
+
+    impl<T> Deref for Json<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+
+    impl<T> DerefMut for Json<T> {
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.0
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned> Field for Json<T> {
+        type Target = Self;
+
+        fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+            let options: JsonObjectOptions = options.into();
+            builder.add_json_field(name, options);
+        }
+
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
+
+            let value = match serde_json::to_value(&value.0) {
+                Ok(value) => value,
+                Err(error) => {
+                    debug_assert!(false, "cannot serialize value into JSON: {error}");
+                    return;
+                }
+            };
+
+            document.add_field_value(field, &OwnedValue::from(value));
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned> Mappable for Json<T> {
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            let value = serde_json::to_value(value).map_err(Error::invalid_value::<T, _>)?;
+
+            serde_json::from_value(value)
+                .map(Json)
+                .map_err(Error::invalid_value::<T, _>)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use json::Json;
+
+#[cfg(feature = "smallvec")]
+mod smallvec {
+    use crate::{
+        Error, Extractable, Field, FieldOptions, FieldTable, Mappable, Queryable, Resolvable,
+    };
+    use smallvec::{Array, SmallVec};
+    use tantivy::schema::*;
+
+    impl<A: Array> Field for SmallVec<A>
+    where
+        A::Item: Mappable,
+    {
+        type Target = Vec<<A::Item as Field>::Target>;
+
+        fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+            A::Item::add_field(builder, name, options);
+        }
+
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            for value in value {
+                A::Item::insert_into_document(document, fields, field_id, value);
+            }
+        }
+    }
+
+    impl<A: Array> Extractable for SmallVec<A>
+    where
+        A::Item: Mappable,
+    {
+        fn extract_from_document(
+            document: &TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+        ) -> Result<Self::Target, Error> {
+            Vec::<A::Item>::extract_from_document(document, fields, field_id)
+        }
+    }
+
+    impl<A: Array> Resolvable for SmallVec<A>
+    where
+        A::Item: Mappable,
+    {
+        type Handle = <A::Item as Resolvable>::Handle;
+
+        fn resolve(schema: &tantivy::schema::Schema, name: &str) -> tantivy::Result<Self::Handle> {
+            A::Item::resolve(schema, name)
+        }
+    }
+
+    impl<A: Array> Queryable for SmallVec<A>
+    where
+        A::Item: Mappable,
+    {
+        type Query = <A::Item as Queryable>::Query;
+
+        fn query(index: &tantivy::Index, handle: Self::Handle) -> Self::Query {
+            A::Item::query(index, handle)
         }
     }
 }
 
 #[cfg(feature = "url")]
 mod url {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use std::str::FromStr as _;
     use tantivy::schema::*;
     use url::Url;
@@ -454,8 +1111,15 @@ mod url {
             builder.add_text_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             let value = value.to_string();
             document.add_text(field, value);
@@ -463,15 +1127,19 @@ mod url {
     }
 
     impl Mappable for Url {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-            value.as_str().and_then(|v| Url::from_str(v).ok())
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            let value = value
+                .as_str()
+                .ok_or_else(|| Error::unexpected_type("str", value))?;
+
+            Url::from_str(value).map_err(Error::invalid_value::<Url, _>)
         }
     }
 }
 
 #[cfg(feature = "uuid")]
 mod uuid {
-    use crate::{Field, FieldOptions, Mappable};
+    use crate::{Error, Field, FieldOptions, FieldTable, Mappable};
     use tantivy::schema::*;
     use uuid::Uuid;
 
@@ -483,8 +1151,15 @@ mod uuid {
             builder.add_text_field(name, options);
         }
 
-        fn insert_into_document(document: &mut TantivyDocument, field_id: u32, value: &Self) {
-            let field = tantivy::schema::Field::from_field_id(field_id);
+        fn insert_into_document(
+            document: &mut TantivyDocument,
+            fields: &FieldTable,
+            field_id: u32,
+            value: &Self,
+        ) {
+            let Some(field) = fields.get(field_id) else {
+                return;
+            };
 
             let value = value.to_string();
             document.add_text(field, value);
@@ -492,8 +1167,12 @@ mod uuid {
     }
 
     impl Mappable for Uuid {
-        fn map_value(value: &OwnedValue) -> Option<Self::Target> {
-            value.as_str().and_then(|v| Uuid::parse_str(v).ok())
+        fn map_value(value: &OwnedValue) -> Result<Self::Target, Error> {
+            let value = value
+                .as_str()
+                .ok_or_else(|| Error::unexpected_type("str", value))?;
+
+            Uuid::parse_str(value).map_err(Error::invalid_value::<Uuid, _>)
         }
     }
 }
@@ -0,0 +1,91 @@
+use crate::index::DEFAULT_MEMORY_BUDGET;
+use crate::{FieldTable, IndexError, Schema, TypedIndexWriter};
+use tantivy::{Index, IndexReader, ReloadPolicy, TantivyDocument};
+
+/// The prefix of the commit payload that records the version of the schema.
+const VERSION_PREFIX: &str = "tantivy-derive:version=";
+
+/// Returns the commit payload that records the version of the schema of `D`.
+pub(crate) fn version_payload<D: Schema>() -> String {
+    format!("{VERSION_PREFIX}{}", D::VERSION)
+}
+
+/// Returns the version of the schema that was recorded by the last commit of a
+/// [`TypedIndexWriter`], or `None` if the index was not committed through one.
+pub fn index_version(index: &Index) -> tantivy::Result<Option<u32>> {
+    let payload = index.load_metas()?.payload;
+
+    Ok(payload
+        .as_deref()
+        .and_then(|payload| payload.strip_prefix(VERSION_PREFIX))
+        .and_then(|version| version.parse().ok()))
+}
+
+/// Checks that the index was either never committed through a [`TypedIndexWriter`] or written
+/// with the version of `D`.
+pub(crate) fn check_version<D: Schema>(index: &Index) -> Result<(), IndexError> {
+    match index_version(index)? {
+        Some(version) if version != D::VERSION => Err(IndexError::Version {
+            expected: D::VERSION,
+            found: version,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Copies the stored documents of `old_index` into `new_index`, converting every document with
+/// `transform`, and returns the number of documents that were copied.
+///
+/// The documents are read one segment at a time, such that the old index does not have to fit in
+/// memory. Only stored fields can be migrated, which means that `Old` is read as
+/// `Old::Target`. The new index is committed once all documents have been added, which records
+/// the version of `New`.
+///
+/// Nothing is copied if either index was written with a different version than the one of its
+/// document type, or if the schema of `new_index` does not match the schema of `New`.
+pub fn migrate<Old, New, F>(
+    old_index: &Index,
+    new_index: &Index,
+    mut transform: F,
+) -> Result<u64, IndexError>
+where
+    Old: Schema,
+    New: Schema,
+    F: FnMut(Old::Target) -> New,
+{
+    check_version::<Old>(old_index)?;
+    check_version::<New>(new_index)?;
+    New::verify_schema(&new_index.schema())?;
+
+    New::register_tokenizers(new_index);
+
+    let old_fields = FieldTable::new::<Old>(&old_index.schema());
+    let new_fields = FieldTable::new::<New>(&new_index.schema());
+
+    let reader: IndexReader = old_index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()?;
+    let searcher = reader.searcher();
+
+    let mut writer =
+        TypedIndexWriter::<New>::new(new_index.writer(DEFAULT_MEMORY_BUDGET)?, new_fields);
+    let mut count = 0;
+
+    for segment_reader in searcher.segment_readers() {
+        let store_reader = segment_reader
+            .get_store_reader(1)
+            .map_err(tantivy::TantivyError::from)?;
+
+        for document in store_reader.iter::<TantivyDocument>(segment_reader.alive_bitset()) {
+            let document = Old::from_document(&document?, &old_fields)?;
+
+            writer.add(&transform(document))?;
+            count += 1;
+        }
+    }
+
+    writer.commit()?;
+
+    Ok(count)
+}
@@ -3,6 +3,7 @@ use tantivy::schema::*;
 #[derive(Clone, Debug, Default)]
 pub struct FieldOptions {
     pub(crate) coerce: bool,
+    pub(crate) expand_dots: bool,
     pub(crate) fast: bool,
     pub(crate) fieldnorms: bool,
     pub(crate) indexed: bool,
@@ -20,6 +21,10 @@ impl FieldOptions {
         self.coerce = value;
     }
 
+    pub fn set_expand_dots(&mut self, value: bool) {
+        self.expand_dots = value;
+    }
+
     pub fn set_fast(&mut self, value: bool) {
         self.fast = value;
     }
@@ -52,6 +57,11 @@ impl FieldOptions {
         self.tokenizer = Some(tokenizer.to_string());
     }
 
+    /// Returns the tokenizer that was set with `#[tantivy(tokenizer = "...")]`, if any.
+    pub fn tokenizer(&self) -> Option<&str> {
+        self.tokenizer.as_deref()
+    }
+
     pub fn set_index_option(&mut self, index_option: IndexRecordOption) {
         self.index_option = Some(index_option);
     }
@@ -149,6 +159,20 @@ impl From<FieldOptions> for IpAddrOptions {
     }
 }
 
+impl From<FieldOptions> for JsonObjectOptions {
+    fn from(value: FieldOptions) -> JsonObjectOptions {
+        let expand_dots = value.expand_dots;
+        let text_options: TextOptions = value.into();
+        let mut options = JsonObjectOptions::from(text_options);
+
+        if expand_dots {
+            options = options.set_expand_dots_enabled();
+        }
+
+        options
+    }
+}
+
 impl From<FieldOptions> for NumericOptions {
     fn from(value: FieldOptions) -> NumericOptions {
         let mut options: NumericOptions = Default::default();
@@ -204,8 +228,11 @@ impl From<FieldOptions> for TextOptions {
         if value.indexed || value.tokenizer.is_some() || value.index_option.is_some() {
             let mut indexing = TextFieldIndexing::default();
 
+            // A `string` field stays untokenized unless a tokenizer is given explicitly.
             if let Some(ref tokenizer) = value.tokenizer {
                 indexing = indexing.set_tokenizer(tokenizer);
+            } else if value.string {
+                indexing = indexing.set_tokenizer("raw");
             }
 
             if let Some(index_option) = value.index_option {
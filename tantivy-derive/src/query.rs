@@ -0,0 +1,152 @@
+use crate::{Field, FieldTable, Resolvable};
+use std::marker::PhantomData;
+use std::ops::Bound;
+use tantivy::query::{BooleanQuery, EmptyQuery, Query, RangeQuery, TermQuery};
+use tantivy::schema::{IndexRecordOption, OwnedValue, TantivyDocument};
+use tantivy::tokenizer::TokenStream as _;
+use tantivy::{Index, Term};
+
+/// Types whose fields can be queried through a typed query builder, such as `DocumentQuery`.
+pub trait Queryable: Resolvable {
+    type Query;
+
+    fn query(index: &Index, handle: Self::Handle) -> Self::Query;
+}
+
+/// Builds queries for a single field of type `T`.
+///
+/// Values are encoded through the [`Field`] implementation of `T`, such that the terms of a query
+/// match the terms that are written when a document is added to the index.
+pub struct FieldQuery<T> {
+    index: Index,
+    field: tantivy::schema::Field,
+    marker: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for FieldQuery<T> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index.clone(),
+            field: self.field,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Field> FieldQuery<T> {
+    pub fn new(index: &Index, field: tantivy::schema::Field) -> Self {
+        Self {
+            index: index.clone(),
+            field,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the schema field that is queried.
+    pub fn field(&self) -> tantivy::schema::Field {
+        self.field
+    }
+
+    /// Returns the terms that are written to the index for the given value.
+    pub fn terms(&self, value: &T) -> Vec<Term> {
+        to_terms(self.field, value)
+    }
+
+    /// Matches the documents in which the field has exactly the given value.
+    ///
+    /// The value is not tokenized, which makes this suitable for `string` fields and non-text
+    /// fields. Use [`FieldQuery::matches`] to search `text` fields instead.
+    pub fn eq(&self, value: impl Into<T>) -> Box<dyn Query> {
+        let queries: Vec<Box<dyn Query>> = self
+            .terms(&value.into())
+            .into_iter()
+            .map(|term| Box::new(TermQuery::new(term, IndexRecordOption::Basic)) as Box<dyn Query>)
+            .collect();
+
+        intersection(queries)
+    }
+
+    /// Matches the documents in which the field has a value between `lower` and `upper`, both
+    /// inclusive.
+    pub fn between(&self, lower: impl Into<T>, upper: impl Into<T>) -> Box<dyn Query> {
+        let lower = self.terms(&lower.into()).into_iter().next();
+        let upper = self.terms(&upper.into()).into_iter().next();
+
+        let (Some(lower), Some(upper)) = (lower, upper) else {
+            return Box::new(EmptyQuery);
+        };
+
+        Box::new(RangeQuery::new(
+            Bound::Included(lower),
+            Bound::Included(upper),
+        ))
+    }
+}
+
+impl FieldQuery<String> {
+    /// Matches the documents in which the field contains any of the tokens of the given text,
+    /// where the text is tokenized with the tokenizer of the field.
+    pub fn matches(&self, text: &str) -> tantivy::Result<Box<dyn Query>> {
+        let mut analyzer = self.index.tokenizer_for_field(self.field)?;
+        let mut stream = analyzer.token_stream(text);
+        let mut queries: Vec<Box<dyn Query>> = vec![];
+
+        while stream.advance() {
+            let term = Term::from_field_text(self.field, &stream.token().text);
+            queries.push(Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs)));
+        }
+
+        Ok(match queries.len() {
+            0 => Box::new(EmptyQuery),
+            1 => queries.remove(0),
+            _ => Box::new(BooleanQuery::union(queries)),
+        })
+    }
+}
+
+impl<T> Queryable for T
+where
+    T: crate::Mappable,
+{
+    type Query = FieldQuery<T>;
+
+    fn query(index: &Index, handle: Self::Handle) -> Self::Query {
+        FieldQuery::new(index, handle)
+    }
+}
+
+/// Returns a query that matches the documents that match all of the given queries.
+fn intersection(mut queries: Vec<Box<dyn Query>>) -> Box<dyn Query> {
+    match queries.len() {
+        0 => Box::new(EmptyQuery),
+        1 => queries.remove(0),
+        _ => Box::new(BooleanQuery::intersection(queries)),
+    }
+}
+
+/// Returns the terms that are written to the given field for the value.
+pub(crate) fn to_terms<T: Field>(field: tantivy::schema::Field, value: &T) -> Vec<Term> {
+    let mut document = TantivyDocument::new();
+    T::insert_into_document(&mut document, &FieldTable::single(field), 0, value);
+
+    document
+        .field_values()
+        .filter_map(|(field, value)| to_term(field, &value.into()))
+        .collect()
+}
+
+/// Converts a value of a document into the term that is written to the index for that value.
+fn to_term(field: tantivy::schema::Field, value: &OwnedValue) -> Option<Term> {
+    Some(match *value {
+        OwnedValue::Str(ref text) => Term::from_field_text(field, text),
+        OwnedValue::U64(value) => Term::from_field_u64(field, value),
+        OwnedValue::I64(value) => Term::from_field_i64(field, value),
+        OwnedValue::F64(value) => Term::from_field_f64(field, value),
+        OwnedValue::Bool(value) => Term::from_field_bool(field, value),
+        OwnedValue::Date(value) => Term::from_field_date_for_search(field, value),
+        OwnedValue::Facet(ref facet) => Term::from_facet(field, facet),
+        OwnedValue::Bytes(ref bytes) => Term::from_field_bytes(field, bytes),
+        OwnedValue::IpAddr(addr) => Term::from_field_ip_addr(field, addr),
+        _ => return None,
+    })
+}
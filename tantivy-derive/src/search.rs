@@ -0,0 +1,22 @@
+/// The query parser settings of a field, as configured by its `search_default`, `boost` and
+/// `fuzzy` attributes.
+#[derive(Clone, Debug)]
+pub struct SearchField {
+    /// The name of the field in the schema.
+    pub name: String,
+    /// Whether the field is searched by queries that do not specify a field.
+    pub default: bool,
+    pub boost: Option<f32>,
+    pub fuzzy: Option<Fuzzy>,
+}
+
+/// The settings for fuzzy term queries on a field.
+#[derive(Clone, Copy, Debug)]
+pub struct Fuzzy {
+    /// The maximum Levenshtein distance, which is at most 2.
+    pub distance: u8,
+    /// Whether terms match as a prefix.
+    pub prefix: bool,
+    /// Whether a transposition counts as a single edit.
+    pub transpose_cost_one: bool,
+}
@@ -0,0 +1,45 @@
+use crate::Schema;
+
+/// Maps the fields of a document type onto the fields of a tantivy schema.
+///
+/// The generated code addresses the fields of a document by their position in the schema built
+/// by [`Schema::schema`]. The default table assumes that the schema was built by exactly that
+/// document type, while [`FieldTable::new`] resolves every field by name against a given schema
+/// instead. Resolving the fields is done once, such that the same table can be reused for every
+/// document that is added to or retrieved from the index.
+///
+/// Fields that do not exist in the given schema are skipped when adding a document and are
+/// considered missing when retrieving a document.
+#[derive(Clone, Debug, Default)]
+pub struct FieldTable {
+    fields: Option<Vec<Option<tantivy::schema::Field>>>,
+}
+
+impl FieldTable {
+    /// Resolves the fields of the document type `D` by name against the given schema.
+    pub fn new<D: Schema>(schema: &tantivy::schema::Schema) -> Self {
+        let fields = D::schema()
+            .fields()
+            .map(|(_, entry)| schema.get_field(entry.name()).ok())
+            .collect();
+
+        Self {
+            fields: Some(fields),
+        }
+    }
+
+    /// Maps the field with position zero onto the given schema field.
+    pub(crate) fn single(field: tantivy::schema::Field) -> Self {
+        Self {
+            fields: Some(vec![Some(field)]),
+        }
+    }
+
+    /// Returns the schema field for the field with the given position, if it exists.
+    pub fn get(&self, field_id: u32) -> Option<tantivy::schema::Field> {
+        match self.fields {
+            Some(ref fields) => fields.get(field_id as usize).copied().flatten(),
+            None => Some(tantivy::schema::Field::from_field_id(field_id)),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use tantivy::schema::{
+    DateTimePrecision, FieldEntry, FieldType, IndexRecordOption, Schema, TextFieldIndexing, Type,
+};
+
+/// A field whose type differs between the two schemas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeChange {
+    pub field: String,
+    pub expected: Type,
+    pub found: Type,
+}
+
+/// A field option that differs between the two schemas, where an option that does not apply to
+/// the field, such as the tokenizer of a field that is not indexed, is `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionChange {
+    pub field: String,
+    pub option: &'static str,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+/// The differences between the schema of a document and the schema of an index, as returned by
+/// [`Schema::verify_schema`](crate::Schema::verify_schema).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SchemaMismatch {
+    /// The fields of the document that do not exist in the index.
+    pub missing: Vec<String>,
+    /// The fields of the index that do not belong to the document.
+    pub extra: Vec<String>,
+    pub type_changes: Vec<TypeChange>,
+    pub option_changes: Vec<OptionChange>,
+}
+
+impl SchemaMismatch {
+    /// Compares the `expected` schema with the schema that was `found`, matching fields by name.
+    pub fn between(expected: &Schema, found: &Schema) -> Self {
+        let mut mismatch = Self::default();
+
+        for (_, expected_entry) in expected.fields() {
+            let name = expected_entry.name();
+
+            let Ok(field) = found.get_field(name) else {
+                mismatch.missing.push(name.to_string());
+                continue;
+            };
+            let found_entry = found.get_field_entry(field);
+
+            let expected_type = expected_entry.field_type().value_type();
+            let found_type = found_entry.field_type().value_type();
+
+            if expected_type != found_type {
+                mismatch.type_changes.push(TypeChange {
+                    field: name.to_string(),
+                    expected: expected_type,
+                    found: found_type,
+                });
+                continue;
+            }
+
+            let options = field_options(expected_entry)
+                .into_iter()
+                .zip(field_options(found_entry));
+
+            for ((option, expected), (_, found)) in options {
+                if expected != found {
+                    mismatch.option_changes.push(OptionChange {
+                        field: name.to_string(),
+                        option,
+                        expected,
+                        found,
+                    });
+                }
+            }
+        }
+
+        for (_, found_entry) in found.fields() {
+            if expected.get_field(found_entry.name()).is_err() {
+                mismatch.extra.push(found_entry.name().to_string());
+            }
+        }
+
+        mismatch
+    }
+
+    /// Returns `true` if the schemas are identical.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.type_changes.is_empty()
+            && self.option_changes.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut differences = vec![];
+
+        for field in &self.missing {
+            differences.push(format!("field `{field}` is missing"));
+        }
+
+        for field in &self.extra {
+            differences.push(format!("field `{field}` is unexpected"));
+        }
+
+        for change in &self.type_changes {
+            differences.push(format!(
+                "field `{}` has type `{}` instead of `{}`",
+                change.field,
+                change.found.name(),
+                change.expected.name()
+            ));
+        }
+
+        for change in &self.option_changes {
+            differences.push(format!(
+                "field `{}` has `{}` set to {} instead of {}",
+                change.field,
+                change.option,
+                change.found.as_deref().unwrap_or("nothing"),
+                change.expected.as_deref().unwrap_or("nothing")
+            ));
+        }
+
+        write!(f, "schema mismatch: {}", differences.join(", "))
+    }
+}
+
+impl std::error::Error for SchemaMismatch {}
+
+/// Returns the options of a field that are compared by [`SchemaMismatch::between`].
+fn field_options(entry: &FieldEntry) -> [(&'static str, Option<String>); 10] {
+    let field_type = entry.field_type();
+    let text_indexing = match *field_type {
+        FieldType::Str(ref options) => options.get_indexing_options(),
+        FieldType::JsonObject(ref options) => options.get_text_indexing_options(),
+        _ => None,
+    };
+    let fast_tokenizer = match *field_type {
+        FieldType::Str(ref options) => options.get_fast_field_tokenizer_name(),
+        FieldType::JsonObject(ref options) => options.get_fast_field_tokenizer_name(),
+        _ => None,
+    };
+    let coerce = match *field_type {
+        FieldType::Str(ref options) => Some(options.should_coerce()),
+        FieldType::U64(ref options)
+        | FieldType::I64(ref options)
+        | FieldType::F64(ref options)
+        | FieldType::Bool(ref options) => Some(options.should_coerce()),
+        _ => None,
+    };
+    let expand_dots = match *field_type {
+        FieldType::JsonObject(ref options) => Some(options.is_expand_dots_enabled()),
+        _ => None,
+    };
+    let precision = match *field_type {
+        FieldType::Date(ref options) => Some(precision_name(options.get_precision())),
+        _ => None,
+    };
+
+    [
+        ("indexed", Some(entry.is_indexed().to_string())),
+        ("stored", Some(entry.is_stored().to_string())),
+        ("fast", Some(entry.is_fast().to_string())),
+        ("fieldnorms", Some(entry.has_fieldnorms().to_string())),
+        (
+            "index_option",
+            text_indexing.map(|indexing| index_option_name(indexing.index_option()).to_string()),
+        ),
+        (
+            "tokenizer",
+            text_indexing
+                .map(TextFieldIndexing::tokenizer)
+                .map(str::to_string),
+        ),
+        ("fast_tokenizer", fast_tokenizer.map(str::to_string)),
+        ("coerce", coerce.map(|coerce| coerce.to_string())),
+        (
+            "expand_dots",
+            expand_dots.map(|expand_dots| expand_dots.to_string()),
+        ),
+        ("precision", precision.map(str::to_string)),
+    ]
+}
+
+/// Returns the name of the index option as used by `#[tantivy(index_option = "...")]`.
+fn index_option_name(option: IndexRecordOption) -> &'static str {
+    match option {
+        IndexRecordOption::Basic => "basic",
+        IndexRecordOption::WithFreqs => "frequency",
+        IndexRecordOption::WithFreqsAndPositions => "frequency-and-position",
+    }
+}
+
+/// Returns the name of the precision as used by `#[tantivy(precision = "...")]`.
+fn precision_name(precision: DateTimePrecision) -> &'static str {
+    match precision {
+        DateTimePrecision::Seconds => "seconds",
+        DateTimePrecision::Milliseconds => "milliseconds",
+        DateTimePrecision::Microseconds => "microseconds",
+        DateTimePrecision::Nanoseconds => "nanoseconds",
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+use tantivy::collector::Count;
+use tantivy::schema::FieldType;
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{DisplayFromStr, Error, FieldTable, Identifiable, Schema, tantivy_document};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sku(String);
+
+impl fmt::Display for Sku {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SKU-{}", self.0)
+    }
+}
+
+impl FromStr for Sku {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .strip_prefix("SKU-")
+            .map(|code| Self(code.to_string()))
+            .ok_or_else(|| format!("invalid SKU `{value}`"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CountryCode(u16);
+
+impl From<CountryCode> for u64 {
+    fn from(code: CountryCode) -> Self {
+        code.0.into()
+    }
+}
+
+impl TryFrom<u64> for CountryCode {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        u16::try_from(value).map(Self)
+    }
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Product {
+    #[tantivy(stored, string, id)]
+    pub id: DisplayFromStr<Sku>,
+    #[tantivy(stored, string, as_string)]
+    pub replaces: Sku,
+    #[tantivy(stored, indexed, via = "u64")]
+    pub country: CountryCode,
+}
+
+fn product() -> Product {
+    Product {
+        id: DisplayFromStr(Sku("1".to_string())),
+        replaces: Sku("0".to_string()),
+        country: CountryCode(528),
+    }
+}
+
+#[test]
+fn reuses_representation() {
+    let schema = Product::schema();
+    let entry = |name| schema.get_field_entry(schema.get_field(name).unwrap());
+
+    assert!(matches!(entry("id").field_type(), FieldType::Str(_)));
+    assert!(matches!(entry("replaces").field_type(), FieldType::Str(_)));
+    assert!(matches!(entry("country").field_type(), FieldType::U64(_)));
+}
+
+#[test]
+fn converts_adapters() {
+    let document: TantivyDocument = product().into();
+
+    assert_eq!(
+        StoredProduct::try_from(document),
+        Ok(StoredProduct {
+            id: Sku("1".to_string()),
+            replaces: Sku("0".to_string()),
+            country: CountryCode(528),
+        })
+    );
+}
+
+#[test]
+fn reports_failed_conversions() {
+    let schema = Product::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("id").unwrap(), "1");
+
+    assert!(matches!(
+        StoredProduct::try_from(document),
+        Err(Error::InvalidValue { ref field, ref message, .. })
+            if field == "id" && message == "invalid SKU `1`"
+    ));
+
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("id").unwrap(), "SKU-1");
+    document.add_text(schema.get_field("replaces").unwrap(), "SKU-0");
+    document.add_u64(schema.get_field("country").unwrap(), 100_000);
+
+    assert!(matches!(
+        StoredProduct::try_from(document),
+        Err(Error::InvalidValue { ref field, .. }) if field == "country"
+    ));
+}
+
+#[test]
+fn queries_adapters() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Product::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    Product::upsert(&writer, &FieldTable::default(), &product())?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let query = ProductQuery::new(&index)?;
+
+    assert_eq!(
+        searcher.search(&query.id().eq(Sku("1".to_string())), &Count)?,
+        1
+    );
+    assert_eq!(
+        searcher.search(&query.replaces().eq(Sku("0".to_string())), &Count)?,
+        1
+    );
+    assert_eq!(
+        searcher.search(&query.country().eq(CountryCode(528)), &Count)?,
+        1
+    );
+    assert_eq!(
+        searcher.search(&query.country().eq(CountryCode(56)), &Count)?,
+        0
+    );
+
+    Ok(())
+}
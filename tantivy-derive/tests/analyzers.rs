@@ -0,0 +1,138 @@
+use tantivy::Index;
+use tantivy::tokenizer::{NgramTokenizer, TokenStream as _, TokenizerManager};
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document]
+#[tantivy(analyzer(name = "words", tokenizer = "whitespace", filters(lowercase)))]
+#[derive(Debug)]
+pub struct Author {
+    #[tantivy(stored, text, tokenizer = "words")]
+    pub name: String,
+}
+
+#[tantivy_document]
+#[tantivy(
+    analyzer(
+        name = "en_full",
+        tokenizer = "simple",
+        filters(
+            lowercase,
+            stopwords = "english",
+            stemmer = "english",
+            remove_long = 10
+        )
+    ),
+    analyzer(name = "folded", filters(ascii_folding, lowercase))
+)]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text, tokenizer = "en_full")]
+    pub body: String,
+    #[tantivy(stored, string, fast_tokenizer = "folded")]
+    pub city: String,
+    #[tantivy(stored, text, tokenizer = "en_stem")]
+    pub title: String,
+    #[tantivy(stored)]
+    pub author: Author,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Product {
+    #[tantivy(stored, text, tokenizer(name = "ngram", external))]
+    pub code: String,
+}
+
+fn tokens(tokenizers: &TokenizerManager, name: &str, text: &str) -> Vec<String> {
+    let mut analyzer = tokenizers.get(name).unwrap();
+    let mut stream = analyzer.token_stream(text);
+    let mut tokens = vec![];
+
+    while stream.advance() {
+        tokens.push(stream.token().text.clone());
+    }
+
+    tokens
+}
+
+#[test]
+fn registers_analyzers() {
+    let index = Index::create_in_ram(Document::schema());
+
+    assert!(index.tokenizers().get("en_full").is_none());
+
+    Document::register_tokenizers(&index);
+
+    assert_eq!(
+        tokens(
+            index.tokenizers(),
+            "en_full",
+            "The Running DOGS of extraordinarily"
+        ),
+        ["run", "dog"]
+    );
+    assert_eq!(
+        tokens(index.fast_field_tokenizer(), "folded", "Zürich"),
+        ["zurich"]
+    );
+    assert_eq!(
+        tokens(index.tokenizers(), "words", "Ernest-Miller Hemingway"),
+        ["ernest-miller", "hemingway"]
+    );
+}
+
+#[test]
+fn indexes_with_analyzers() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Document::schema());
+    Document::register_tokenizers(&index);
+
+    let mut writer: tantivy::IndexWriter = index.writer(15_000_000)?;
+    writer.add_document(
+        Document {
+            body: "The dogs are running".to_string(),
+            city: "Zürich".to_string(),
+            title: "Dogs".to_string(),
+            author: Author {
+                name: "Ernest Hemingway".to_string(),
+            },
+        }
+        .into(),
+    )?;
+    writer.commit()?;
+
+    let schema = index.schema();
+    let body = schema.get_field("body").unwrap();
+    let query = tantivy::query::QueryParser::for_index(&index, vec![body]).parse_query("run")?;
+    let searcher = index.reader()?.searcher();
+
+    assert_eq!(searcher.search(&query, &tantivy::collector::Count)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn uses_external_tokenizers() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Product::schema());
+    index
+        .tokenizers()
+        .register("ngram", NgramTokenizer::new(2, 3, false)?);
+    Product::register_tokenizers(&index);
+
+    let mut writer: tantivy::IndexWriter = index.writer(15_000_000)?;
+    writer.add_document(
+        Product {
+            code: "abc".to_string(),
+        }
+        .into(),
+    )?;
+    writer.commit()?;
+
+    let schema = index.schema();
+    let code = schema.get_field("code").unwrap();
+    let query = tantivy::query::QueryParser::for_index(&index, vec![code]).parse_query("bc")?;
+    let searcher = index.reader()?.searcher();
+
+    assert_eq!(searcher.search(&query, &tantivy::collector::Count)?, 1);
+
+    Ok(())
+}
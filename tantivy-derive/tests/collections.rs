@@ -0,0 +1,106 @@
+#![cfg(feature = "smallvec")]
+
+use smallvec::{SmallVec, smallvec};
+use std::collections::{BTreeSet, HashSet};
+use tantivy::TantivyDocument;
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, string)]
+    pub tags: Vec<String>,
+    #[tantivy(stored, indexed)]
+    pub years: BTreeSet<u64>,
+    #[tantivy(stored, string)]
+    pub authors: HashSet<String>,
+    #[tantivy(stored)]
+    pub ratings: SmallVec<[i64; 2]>,
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+fn round_trip(document: Document) -> StoredDocument {
+    let document: TantivyDocument = document.into();
+
+    StoredDocument::try_from(document).unwrap()
+}
+
+#[test]
+fn adds_a_single_field() {
+    let schema = Document::schema();
+    let names: Vec<_> = schema
+        .fields()
+        .map(|(_, entry)| entry.name().to_string())
+        .collect();
+
+    assert_eq!(names, ["tags", "years", "authors", "ratings", "title"]);
+}
+
+#[test]
+fn empty() {
+    let document = round_trip(Document {
+        tags: vec![],
+        years: BTreeSet::new(),
+        authors: HashSet::new(),
+        ratings: smallvec![],
+        title: "Cannery Row".to_string(),
+    });
+
+    assert!(document.tags.is_empty());
+    assert!(document.years.is_empty());
+    assert!(document.authors.is_empty());
+    assert!(document.ratings.is_empty());
+    assert_eq!(document.title, "Cannery Row");
+}
+
+#[test]
+fn single_element() {
+    let document = round_trip(Document {
+        tags: vec!["novel".to_string()],
+        years: BTreeSet::from([1945]),
+        authors: HashSet::from(["John Steinbeck".to_string()]),
+        ratings: smallvec![-1],
+        title: "Cannery Row".to_string(),
+    });
+
+    assert_eq!(document.tags, ["novel"]);
+    assert_eq!(document.years, BTreeSet::from([1945]));
+    assert_eq!(
+        document.authors,
+        HashSet::from(["John Steinbeck".to_string()])
+    );
+    assert_eq!(document.ratings, [-1]);
+    assert_eq!(document.title, "Cannery Row");
+}
+
+#[test]
+fn many_elements() {
+    let schema = Document::schema();
+    let document: TantivyDocument = Document {
+        tags: vec![
+            "novel".to_string(),
+            "fiction".to_string(),
+            "classic".to_string(),
+        ],
+        years: BTreeSet::from([1945, 1954, 1982]),
+        authors: HashSet::from(["John Steinbeck".to_string(), "Unknown".to_string()]),
+        ratings: smallvec![4, 5, -2],
+        title: "Cannery Row".to_string(),
+    }
+    .into();
+
+    let tags = schema.get_field("tags").unwrap();
+    let title = schema.get_field("title").unwrap();
+
+    assert_eq!(document.get_all(tags).count(), 3);
+    assert_eq!(document.get_all(title).count(), 1);
+
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.tags, ["novel", "fiction", "classic"]);
+    assert_eq!(document.years, BTreeSet::from([1945, 1954, 1982]));
+    assert_eq!(document.authors.len(), 2);
+    assert_eq!(document.ratings, [4, 5, -2]);
+    assert_eq!(document.title, "Cannery Row");
+}
@@ -0,0 +1,9 @@
+use tantivy_derive::Schema;
+
+/// Returns the names of the fields of the schema of `D`, in the order in which they were added.
+pub fn field_names<D: Schema>() -> Vec<String> {
+    D::schema()
+        .fields()
+        .map(|(_, entry)| entry.name().to_string())
+        .collect()
+}
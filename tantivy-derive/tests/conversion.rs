@@ -0,0 +1,81 @@
+#![cfg(feature = "uuid")]
+
+use tantivy::TantivyDocument;
+use tantivy::schema::OwnedValue;
+use tantivy_derive::{Error, Schema, tantivy_document};
+use uuid::Uuid;
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, string)]
+    pub id: Uuid,
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored)]
+    pub subtitle: Option<String>,
+}
+
+#[test]
+fn round_trip() {
+    let id = Uuid::from_u128(0x1234);
+    let document: TantivyDocument = Document {
+        id,
+        title: "The Old Man and the Sea".to_string(),
+        subtitle: None,
+    }
+    .into();
+
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.id, id);
+    assert_eq!(document.title, "The Old Man and the Sea");
+    assert_eq!(document.subtitle, None);
+}
+
+#[test]
+fn reports_missing_field() {
+    let schema = Document::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("id").unwrap(), Uuid::nil().to_string());
+
+    let error = StoredDocument::try_from(document).unwrap_err();
+
+    assert_eq!(
+        error,
+        Error::MissingField {
+            field: "title".to_string()
+        }
+    );
+    assert_eq!(error.to_string(), "missing value for field `title`");
+}
+
+#[test]
+fn reports_unexpected_type() {
+    let schema = Document::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("id").unwrap(), Uuid::nil().to_string());
+    document.add_field_value(schema.get_field("title").unwrap(), &OwnedValue::U64(42));
+
+    let error = StoredDocument::try_from(document).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "unexpected value for field `title`: expected str, got u64"
+    );
+}
+
+#[test]
+fn reports_invalid_value() {
+    let schema = Document::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("id").unwrap(), "not-a-uuid");
+    document.add_text(schema.get_field("title").unwrap(), "Of Mice and Men");
+
+    let error = StoredDocument::try_from(document).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::InvalidValue { ref field, ty: "uuid::Uuid", .. } if field == "id"
+    ));
+}
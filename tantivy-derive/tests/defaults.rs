@@ -0,0 +1,101 @@
+use tantivy::TantivyDocument;
+use tantivy::schema::{STORED, Schema as TantivySchema, TEXT};
+use tantivy_derive::{Error, FieldTable, Schema, tantivy_document};
+
+fn unknown_language() -> String {
+    "unknown".to_string()
+}
+
+#[tantivy_document]
+#[tantivy(view(name = "Summary", fields(title, language)))]
+#[derive(Debug, PartialEq)]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, indexed, default)]
+    pub pages: u64,
+    #[tantivy(stored, string, default = "unknown_language")]
+    pub language: String,
+    #[tantivy(stored, indexed)]
+    pub year: u64,
+}
+
+/// The schema of the index before `pages` and `language` were added.
+fn old_schema() -> TantivySchema {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field("title", TEXT | STORED);
+    builder.add_u64_field("year", STORED);
+    builder.build()
+}
+
+fn old_document(schema: &TantivySchema) -> TantivyDocument {
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("title").unwrap(), "The Sea Wolf");
+    document.add_u64(schema.get_field("year").unwrap(), 1904);
+    document
+}
+
+#[test]
+fn fills_in_missing_fields() {
+    let schema = old_schema();
+    let fields = FieldTable::new::<Book>(&schema);
+
+    assert_eq!(
+        Book::from_document(&old_document(&schema), &fields),
+        Ok(StoredBook {
+            title: "The Sea Wolf".to_string(),
+            pages: 0,
+            language: "unknown".to_string(),
+            year: 1904,
+        })
+    );
+}
+
+#[test]
+fn fills_in_missing_fields_of_views() {
+    let schema = old_schema();
+    let fields = FieldTable::new::<Book>(&schema);
+
+    assert_eq!(
+        <BookSummary as tantivy_derive::View>::from_document(&old_document(&schema), &fields),
+        Ok(BookSummary {
+            title: "The Sea Wolf".to_string(),
+            language: "unknown".to_string(),
+        })
+    );
+}
+
+#[test]
+fn keeps_present_fields() {
+    let document: TantivyDocument = Book {
+        title: "White Fang".to_string(),
+        pages: 298,
+        language: "english".to_string(),
+        year: 1906,
+    }
+    .into();
+
+    assert_eq!(
+        StoredBook::try_from(document),
+        Ok(StoredBook {
+            title: "White Fang".to_string(),
+            pages: 298,
+            language: "english".to_string(),
+            year: 1906,
+        })
+    );
+}
+
+#[test]
+fn reports_fields_without_default() {
+    let schema = Book::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("title").unwrap(), "The Sea Wolf");
+
+    assert_eq!(
+        StoredBook::try_from(document),
+        Err(Error::MissingField {
+            field: "year".to_string()
+        })
+    );
+}
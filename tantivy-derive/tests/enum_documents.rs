@@ -0,0 +1,218 @@
+mod common;
+
+use common::field_names;
+use tantivy::collector::Count;
+use tantivy::query::TermQuery;
+use tantivy::schema::{IndexRecordOption, OwnedValue};
+use tantivy::{Index, IndexWriter, TantivyDocument, Term};
+use tantivy_derive::{Error, Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Item {
+    #[tantivy(stored, string)]
+    pub sku: String,
+    #[tantivy(stored)]
+    pub quantity: u64,
+}
+
+#[tantivy_document]
+#[tantivy(tag = "kind", rename_all = "snake_case")]
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    Click {
+        #[tantivy(stored, string)]
+        url: String,
+        #[tantivy(indexed)]
+        x: u64,
+    },
+    #[tantivy(rename_all = "camelCase")]
+    Purchase {
+        #[tantivy(stored, indexed)]
+        total_amount: u64,
+        #[tantivy(stored)]
+        item: Item,
+    },
+    LoggedOut,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub enum Action {
+    Click {
+        #[tantivy(stored, string)]
+        url: String,
+    },
+    #[tantivy(rename = "Log-Out")]
+    LogOut,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Log {
+    #[tantivy(stored)]
+    pub timestamp: u64,
+    #[tantivy(stored)]
+    pub event: Event,
+}
+
+#[test]
+fn unions_variant_fields() {
+    assert_eq!(
+        field_names::<Event>(),
+        [
+            "kind",
+            "url",
+            "x",
+            "totalAmount",
+            "item.sku",
+            "item.quantity"
+        ]
+    );
+    assert_eq!(
+        field_names::<Log>(),
+        [
+            "timestamp",
+            "event.kind",
+            "event.url",
+            "event.x",
+            "event.totalAmount",
+            "event.item.sku",
+            "event.item.quantity"
+        ]
+    );
+}
+
+#[test]
+fn stores_tag() {
+    let schema = Event::schema();
+    let event = Event::Purchase {
+        total_amount: 42,
+        item: Item {
+            sku: "A-1".to_string(),
+            quantity: 2,
+        },
+    };
+    let document: TantivyDocument = event.into();
+
+    let values: Vec<(String, OwnedValue)> = document
+        .field_values()
+        .map(|(field, value)| {
+            (
+                schema.get_field_name(field).to_string(),
+                OwnedValue::from(value),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        [
+            ("kind".to_string(), OwnedValue::Str("purchase".into())),
+            ("totalAmount".to_string(), OwnedValue::U64(42)),
+            ("item.sku".to_string(), OwnedValue::Str("A-1".into())),
+            ("item.quantity".to_string(), OwnedValue::U64(2)),
+        ]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let events = [
+        Event::Click {
+            url: "https://example.com".to_string(),
+            x: 3,
+        },
+        Event::Purchase {
+            total_amount: 42,
+            item: Item {
+                sku: "A-1".to_string(),
+                quantity: 2,
+            },
+        },
+        Event::LoggedOut,
+    ];
+    let expected = [
+        StoredEvent::Click {
+            url: "https://example.com".to_string(),
+        },
+        StoredEvent::Purchase {
+            total_amount: 42,
+            item: StoredItem {
+                sku: "A-1".to_string(),
+                quantity: 2,
+            },
+        },
+        StoredEvent::LoggedOut,
+    ];
+
+    for (event, expected) in events.into_iter().zip(expected) {
+        let document: TantivyDocument = Log {
+            timestamp: 1,
+            event,
+        }
+        .into();
+        let log = StoredLog::try_from(document).unwrap();
+
+        assert_eq!(log.event, expected);
+    }
+}
+
+#[test]
+fn resolves_handles() {
+    let schema = Log::schema();
+    let fields = LogFields::from_schema(&schema).unwrap();
+
+    assert_eq!(schema.get_field_name(fields.event.tag), "event.kind");
+    assert_eq!(
+        schema.get_field_name(fields.event.purchase_total_amount),
+        "event.totalAmount"
+    );
+    assert_eq!(
+        schema.get_field_name(fields.event.purchase_item.sku),
+        "event.item.sku"
+    );
+}
+
+#[test]
+fn rejects_unknown_variants() {
+    let schema = Event::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("kind").unwrap(), "scroll");
+
+    let error = StoredEvent::try_from(document).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::InvalidValue { ref field, ref message, .. }
+            if field == "kind" && message.contains("scroll")
+    ));
+}
+
+#[test]
+fn queries_tag_terms() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Action::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    writer.add_document(
+        Action::Click {
+            url: "https://example.com".to_string(),
+        }
+        .into(),
+    )?;
+    writer.add_document(Action::LogOut.into())?;
+    writer.commit()?;
+
+    let fields = ActionFields::from_schema(&index.schema())?;
+    let searcher = index.reader()?.searcher();
+
+    for tag in ["Click", "Log-Out"] {
+        let query = TermQuery::new(
+            Term::from_field_text(fields.tag, tag),
+            IndexRecordOption::Basic,
+        );
+
+        assert_eq!(searcher.search(&query, &Count)?, 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use tantivy::TantivyDocument;
+use tantivy::schema::{FieldType, OwnedValue};
+use tantivy_derive::{Error, Field, Schema, tantivy_document};
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(rename_all = "snake_case")]
+pub enum Status {
+    Draft,
+    InReview,
+    #[tantivy(rename = "live")]
+    Published,
+}
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(repr = "u64")]
+pub enum Priority {
+    Low = 1,
+    High = 10,
+}
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(repr = "facet", rename_all = "lowercase")]
+pub enum Category {
+    Fiction,
+    #[tantivy(rename = "/non-fiction/history")]
+    History,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, indexed)]
+    pub status: Status,
+    #[tantivy(stored, indexed, fast)]
+    pub priority: Priority,
+    #[tantivy(stored, indexed)]
+    pub category: Category,
+    #[tantivy(stored)]
+    pub tags: Vec<Status>,
+}
+
+fn document() -> Document {
+    Document {
+        status: Status::InReview,
+        priority: Priority::High,
+        category: Category::History,
+        tags: vec![Status::Draft, Status::Published],
+    }
+}
+
+#[test]
+fn uses_representation() {
+    let schema = Document::schema();
+    let field_type = |name| {
+        schema
+            .get_field_entry(schema.get_field(name).unwrap())
+            .field_type()
+            .clone()
+    };
+
+    assert!(matches!(field_type("status"), FieldType::Str(_)));
+    assert!(matches!(field_type("priority"), FieldType::U64(_)));
+    assert!(matches!(field_type("category"), FieldType::Facet(_)));
+
+    let FieldType::Str(options) = field_type("status") else {
+        unreachable!();
+    };
+
+    assert_eq!(options.get_indexing_options().unwrap().tokenizer(), "raw");
+}
+
+#[test]
+fn stores_variant_names() {
+    let schema = Document::schema();
+    let document: TantivyDocument = document().into();
+    let values = |name| {
+        document
+            .get_all(schema.get_field(name).unwrap())
+            .map(OwnedValue::from)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(values("status"), [OwnedValue::Str("in_review".into())]);
+    assert_eq!(values("priority"), [OwnedValue::U64(10)]);
+    assert_eq!(
+        values("category"),
+        [OwnedValue::Facet("/non-fiction/history".into())]
+    );
+    assert_eq!(
+        values("tags"),
+        [
+            OwnedValue::Str("draft".into()),
+            OwnedValue::Str("live".into())
+        ]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = document().into();
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.status, Status::InReview);
+    assert_eq!(document.priority, Priority::High);
+    assert_eq!(document.category, Category::History);
+    assert_eq!(document.tags, [Status::Draft, Status::Published]);
+}
+
+#[test]
+fn rejects_unknown_variants() {
+    let schema = Document::schema();
+    let status = schema.get_field("status").unwrap();
+    let document: TantivyDocument = document().into();
+
+    let mut replaced = TantivyDocument::new();
+
+    for (f, v) in document.field_values() {
+        if f == status {
+            replaced.add_text(status, "archived");
+        } else {
+            replaced.add_field_value(f, v);
+        }
+    }
+
+    let error = StoredDocument::try_from(replaced).unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::InvalidValue { ref field, ref message, .. }
+            if field == "status" && message.contains("archived")
+    ));
+}
@@ -0,0 +1,30 @@
+use tantivy::TantivyDocument;
+use tantivy::schema::Facet;
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored)]
+    pub category: Facet,
+    #[tantivy(stored)]
+    pub tags: Vec<Facet>,
+    #[tantivy(stored)]
+    pub root: Facet,
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = Document {
+        category: Facet::from("/non-fiction/history"),
+        tags: vec![Facet::from("/lang/en"), Facet::from("/format/paperback")],
+        root: Facet::root(),
+    }
+    .into();
+
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.category, "/non-fiction/history");
+    assert_eq!(document.tags, ["/lang/en", "/format/paperback"]);
+    assert_eq!(document.root, "/");
+}
@@ -0,0 +1,31 @@
+use tantivy::schema::{STORED, Schema as TantivySchema, TEXT};
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document(fields = "BookHandles")]
+#[derive(Debug)]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub _title: String,
+    #[tantivy(text)]
+    pub body: String,
+}
+
+#[test]
+fn resolves_handles() {
+    let schema = Book::schema();
+    let fields = BookHandles::from_schema(&schema).unwrap();
+
+    assert_eq!(fields._title, schema.get_field("title").unwrap());
+    assert_eq!(fields.body, schema.get_field("body").unwrap());
+}
+
+#[test]
+fn reports_missing_field() {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field("title", TEXT | STORED);
+    let schema = builder.build();
+
+    let error = BookHandles::from_schema(&schema).unwrap_err();
+
+    assert!(matches!(error, tantivy::TantivyError::FieldNotFound(ref name) if name == "body"));
+}
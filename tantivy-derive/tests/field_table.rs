@@ -0,0 +1,65 @@
+use tantivy::schema::{STORED, STRING, Schema as TantivySchema, TEXT};
+use tantivy_derive::{Error, FieldTable, Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, indexed)]
+    pub pages: u64,
+}
+
+fn foreign_schema() -> TantivySchema {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field("id", STRING | STORED);
+    builder.add_u64_field("pages", STORED);
+    builder.add_text_field("title", TEXT | STORED);
+    builder.build()
+}
+
+#[test]
+fn resolves_fields_by_name() {
+    let schema = foreign_schema();
+    let fields = FieldTable::new::<Document>(&schema);
+
+    let document = Document {
+        title: "The Old Man and the Sea".to_string(),
+        pages: 127,
+    };
+    let document = document.to_document(&fields);
+
+    let title = schema.get_field("title").unwrap();
+    let pages = schema.get_field("pages").unwrap();
+
+    assert_eq!(document.len(), 2);
+    assert!(document.get_first(title).is_some());
+    assert!(document.get_first(pages).is_some());
+
+    let stored = Document::from_document(&document, &fields).unwrap();
+
+    assert_eq!(stored.title, "The Old Man and the Sea");
+    assert_eq!(stored.pages, 127);
+}
+
+#[test]
+fn missing_fields_are_skipped() {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field("title", TEXT | STORED);
+    let schema = builder.build();
+    let fields = FieldTable::new::<Document>(&schema);
+
+    let document = Document {
+        title: "Of Mice and Men".to_string(),
+        pages: 107,
+    };
+    let document = document.to_document(&fields);
+
+    assert_eq!(document.len(), 1);
+    assert_eq!(
+        Document::from_document(&document, &fields).unwrap_err(),
+        Error::MissingField {
+            field: "pages".to_string()
+        },
+    );
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::field_names;
+use std::fmt::Debug;
+use tantivy::collector::Count;
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{Field, FieldTable, Identifiable, Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Author {
+    #[tantivy(stored, text)]
+    pub name: String,
+}
+
+#[tantivy_document]
+pub struct Envelope<M, K = u64> {
+    #[tantivy(stored, indexed, id)]
+    pub key: u64,
+    #[tantivy(stored)]
+    pub meta: M,
+    #[tantivy(stored, indexed)]
+    pub tags: Vec<K>,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Indexed<T>
+where
+    T: Debug,
+{
+    #[tantivy(stored, string)]
+    pub name: String,
+    #[tantivy(indexed)]
+    pub value: T,
+}
+
+#[tantivy_document]
+#[tantivy(view(name = "Name", fields(name)))]
+#[derive(Debug, PartialEq)]
+pub struct Runtime<C: Clone + Default> {
+    #[tantivy(stored, string)]
+    pub name: String,
+    #[tantivy(skip)]
+    pub cache: C,
+}
+
+#[tantivy_document]
+#[tantivy(rename_all = "snake_case")]
+pub enum Event<P> {
+    Created {
+        #[tantivy(stored)]
+        payload: P,
+    },
+    Deleted,
+}
+
+#[test]
+fn builds_schema_of_parameters() {
+    assert_eq!(
+        field_names::<Envelope<Author>>(),
+        ["key", "meta.name", "tags"]
+    );
+    assert_eq!(
+        field_names::<Envelope<String, i64>>(),
+        ["key", "meta", "tags"]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let envelope = Envelope {
+        key: 1,
+        meta: Author {
+            name: "Ursula".to_string(),
+        },
+        tags: vec![2u64, 3],
+    };
+
+    let document: TantivyDocument = envelope.into();
+    let envelope = StoredEnvelope::<Author>::try_from(document).unwrap();
+
+    assert_eq!(envelope.key, 1);
+    assert_eq!(envelope.meta.name, "Ursula");
+    assert_eq!(envelope.tags, [2, 3]);
+}
+
+#[test]
+fn marks_unused_parameters() {
+    let document: TantivyDocument = Indexed {
+        name: "answer".to_string(),
+        value: 42u64,
+    }
+    .into();
+    let indexed = StoredIndexed::<u64>::try_from(document).unwrap();
+
+    assert_eq!(indexed.name, "answer");
+
+    let document: TantivyDocument = Runtime {
+        name: "cached".to_string(),
+        cache: vec![1u8],
+    }
+    .into();
+
+    assert_eq!(
+        RuntimeName::<Vec<u8>>::try_from(document.clone())
+            .unwrap()
+            .name,
+        "cached"
+    );
+    assert_eq!(
+        StoredRuntime::<Vec<u8>>::try_from(document).unwrap(),
+        StoredRuntime {
+            name: "cached".to_string(),
+            _marker: Default::default(),
+        }
+    );
+}
+
+#[test]
+fn queries_parameters() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Envelope::<String>::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    let envelope = Envelope {
+        key: 7,
+        meta: "meta".to_string(),
+        tags: vec![8u64],
+    };
+
+    Envelope::upsert(&writer, &FieldTable::default(), &envelope)?;
+    Envelope::upsert(&writer, &FieldTable::default(), &envelope)?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let query = EnvelopeQuery::<String>::new(&index)?;
+    let fields = EnvelopeFields::<String>::from_schema(&index.schema())?;
+
+    assert_eq!(searcher.search(&query.key().eq(7u64), &Count)?, 1);
+    assert_eq!(searcher.search(&query.tags().eq(8u64), &Count)?, 1);
+    assert_eq!(
+        format!("{fields:?}"),
+        "EnvelopeFields { key: Field(0), meta: Field(1), tags: Field(2) }"
+    );
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct Id<T>(T);
+
+#[test]
+fn accepts_generic_fields() {
+    let document: TantivyDocument = Envelope {
+        key: 5,
+        meta: Id(-5i64),
+        tags: vec![Id(6u64)],
+    }
+    .into();
+    let envelope = StoredEnvelope::<Id<i64>, Id<u64>>::try_from(document).unwrap();
+
+    assert_eq!(envelope.key, 5);
+    assert_eq!(envelope.meta, Id(-5));
+    assert_eq!(envelope.tags, [Id(6)]);
+}
+
+#[test]
+fn supports_generic_enums() {
+    assert_eq!(field_names::<Event<Author>>(), ["type", "payload.name"]);
+
+    let document: TantivyDocument = Event::Created { payload: 3u64 }.into();
+
+    assert!(matches!(
+        StoredEvent::<u64>::try_from(document),
+        Ok(StoredEvent::Created { payload: 3 })
+    ));
+}
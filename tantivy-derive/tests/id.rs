@@ -0,0 +1,244 @@
+use tantivy::collector::{Count, TopDocs};
+use tantivy::query::AllQuery;
+use tantivy::schema::{INDEXED, STORED, STRING, Schema as TantivySchema, TEXT};
+use tantivy::{Index, IndexWriter, TantivyDocument, Term};
+use tantivy_derive::{FieldTable, Identifiable, Schema, TypedIndex, tantivy_document};
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, string, id)]
+    pub isbn: String,
+    #[tantivy(stored, indexed)]
+    pub year: u64,
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Edition {
+    #[tantivy(stored)]
+    pub book: Book,
+    #[tantivy(stored, indexed, id)]
+    pub number: u64,
+}
+
+#[cfg(feature = "uuid")]
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Author {
+    #[tantivy(stored, string, id)]
+    pub id: Uuid,
+    #[tantivy(stored, text)]
+    pub name: String,
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Product {
+    #[tantivy(stored, string, indexed, id)]
+    pub sku: String,
+    #[tantivy(stored, indexed)]
+    pub stock: u64,
+}
+
+fn book(title: &str, isbn: &str, year: u64) -> Book {
+    Book {
+        title: title.to_string(),
+        isbn: isbn.to_string(),
+        year,
+    }
+}
+
+#[test]
+fn id_term() {
+    let schema = Book::schema();
+    let isbn = schema.get_field("isbn").unwrap();
+
+    assert_eq!(
+        book("The Sea Wolf", "978-0", 1904)
+            .id_term(&FieldTable::default())
+            .unwrap(),
+        Term::from_field_text(isbn, "978-0")
+    );
+
+    let schema = Edition::schema();
+    let number = schema.get_field("number").unwrap();
+    let edition = Edition {
+        book: book("The Sea Wolf", "978-0", 1904),
+        number: 2,
+    };
+
+    assert_eq!(
+        edition.id_term(&FieldTable::default()).unwrap(),
+        Term::from_field_u64(number, 2)
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_id_term() {
+    let schema = Author::schema();
+    let field = schema.get_field("id").unwrap();
+    let id = Uuid::from_u128(42);
+    let author = Author {
+        id,
+        name: "Jack London".to_string(),
+    };
+
+    assert_eq!(
+        author.id_term(&FieldTable::default()).unwrap(),
+        Term::from_field_text(field, &id.to_string())
+    );
+}
+
+#[test]
+fn upserts_and_deletes() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Book::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    let fields = FieldTable::new::<Book>(&index.schema());
+
+    Book::upsert(&writer, &fields, &book("The Sea Wolf", "978-0", 1903))?;
+    Book::upsert(&writer, &fields, &book("White Fang", "978-1", 1906))?;
+    writer.commit()?;
+
+    Book::upsert(&writer, &fields, &book("The Sea Wolf", "978-0", 1904))?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let top_docs = searcher.search(&AllQuery, &TopDocs::with_limit(10))?;
+    let mut books = top_docs
+        .into_iter()
+        .map(|(_, address)| {
+            let document: TantivyDocument = searcher.doc(address)?;
+
+            Ok(StoredBook::try_from(document).unwrap())
+        })
+        .collect::<tantivy::Result<Vec<_>>>()?;
+    books.sort_by_key(|book| book.year);
+
+    assert_eq!(
+        books,
+        [
+            StoredBook {
+                title: "The Sea Wolf".to_string(),
+                isbn: "978-0".to_string(),
+                year: 1904,
+            },
+            StoredBook {
+                title: "White Fang".to_string(),
+                isbn: "978-1".to_string(),
+                year: 1906,
+            },
+        ]
+    );
+
+    Book::delete(&writer, &fields, &"978-1".to_string())?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+
+    assert_eq!(searcher.search(&AllQuery, &Count)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn upserts_into_schema_in_other_order() -> tantivy::Result<()> {
+    let mut builder = TantivySchema::builder();
+    builder.add_u64_field("year", INDEXED | STORED);
+    builder.add_text_field("isbn", STRING | STORED);
+    builder.add_text_field("title", TEXT | STORED);
+    let index = Index::create_in_ram(builder.build());
+
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    let fields = FieldTable::new::<Book>(&index.schema());
+
+    Book::upsert(&writer, &fields, &book("The Sea Wolf", "978-0", 1903))?;
+    Book::upsert(&writer, &fields, &book("The Sea Wolf", "978-0", 1904))?;
+    Book::upsert(&writer, &fields, &book("White Fang", "978-1", 1906))?;
+    Book::delete(&writer, &fields, &"978-1".to_string())?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let top_docs = searcher.search(&AllQuery, &TopDocs::with_limit(10))?;
+    let document: TantivyDocument = searcher.doc(top_docs[0].1)?;
+
+    assert_eq!(top_docs.len(), 1);
+    assert_eq!(Book::from_document(&document, &fields).unwrap().year, 1904);
+
+    Ok(())
+}
+
+#[test]
+fn upserts_indexed_string_ids() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Product::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    let fields = FieldTable::new::<Product>(&index.schema());
+
+    for stock in [1, 2] {
+        let product = Product {
+            sku: "ABC-1/Red".to_string(),
+            stock,
+        };
+        Product::upsert(&writer, &fields, &product)?;
+    }
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let top_docs = searcher.search(&AllQuery, &TopDocs::with_limit(10))?;
+    let document: TantivyDocument = searcher.doc(top_docs[0].1)?;
+
+    assert_eq!(top_docs.len(), 1);
+    assert_eq!(Product::from_document(&document, &fields).unwrap().stock, 2);
+
+    Ok(())
+}
+
+#[test]
+fn reports_missing_id_field() -> tantivy::Result<()> {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field("title", TEXT | STORED);
+    let index = Index::create_in_ram(builder.build());
+
+    let writer: IndexWriter = index.writer(15_000_000)?;
+    let fields = FieldTable::new::<Book>(&index.schema());
+
+    assert!(Book::upsert(&writer, &fields, &book("The Sea Wolf", "978-0", 1904)).is_err());
+    assert!(Book::delete(&writer, &fields, &"978-0".to_string()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn typed_index_upserts_and_deletes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut index = TypedIndex::<Edition>::create_in_ram()?;
+
+    index.upsert(&Edition {
+        book: book("The Sea Wolf", "978-0", 1904),
+        number: 1,
+    })?;
+    index.upsert(&Edition {
+        book: book("The Sea Wolf", "978-0", 1904),
+        number: 2,
+    })?;
+    index.commit()?;
+
+    index.upsert(&Edition {
+        book: book("The Sea-Wolf", "978-0", 1904),
+        number: 1,
+    })?;
+    index.delete(&2)?;
+    index.commit()?;
+
+    let results = index.search(&AllQuery, 10)?;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1.number, 1);
+    assert_eq!(results[0].1.book.title, "The Sea-Wolf");
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::schema::FieldType;
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{Json, Schema, tantivy_document};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Metadata {
+    pub color: String,
+    pub sizes: Vec<u64>,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, indexed, fast)]
+    pub metadata: Json<Metadata>,
+    #[tantivy(stored, indexed, expand_dots)]
+    pub attributes: Json<BTreeMap<String, String>>,
+}
+
+fn document() -> Document {
+    Document {
+        title: "T-shirt".to_string(),
+        metadata: Json(Metadata {
+            color: "red".to_string(),
+            sizes: vec![38, 40],
+        }),
+        attributes: Json(BTreeMap::from([(
+            "fabric.kind".to_string(),
+            "cotton".to_string(),
+        )])),
+    }
+}
+
+#[test]
+fn adds_json_fields() {
+    let schema = Document::schema();
+    let entry = schema.get_field_entry(schema.get_field("metadata").unwrap());
+
+    let FieldType::JsonObject(options) = entry.field_type() else {
+        panic!("expected a JSON field");
+    };
+
+    assert!(options.is_stored());
+    assert!(options.is_indexed());
+    assert!(options.is_fast());
+    assert!(!options.is_expand_dots_enabled());
+
+    let entry = schema.get_field_entry(schema.get_field("attributes").unwrap());
+
+    let FieldType::JsonObject(options) = entry.field_type() else {
+        panic!("expected a JSON field");
+    };
+
+    assert!(options.is_expand_dots_enabled());
+}
+
+#[test]
+fn round_trip() {
+    let expected = document();
+    let document: TantivyDocument = document().into();
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.metadata, expected.metadata);
+    assert_eq!(document.attributes, expected.attributes);
+}
+
+#[test]
+fn checks_serialization() {
+    assert!(Json::try_new(BTreeMap::from([("size".to_string(), 38)])).is_ok());
+    assert!(Json::try_new(BTreeMap::from([(vec![38, 40], "small".to_string())])).is_err());
+}
+
+#[test]
+fn search() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Document::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    writer.add_document(document().into())?;
+    writer.commit()?;
+
+    let fields = DocumentFields::from_schema(&index.schema())?;
+    let query_parser = QueryParser::for_index(&index, vec![fields.title]);
+    let searcher = index.reader()?.searcher();
+
+    for query in ["metadata.color:red", "attributes.fabric.kind:cotton"] {
+        let query = query_parser.parse_query(query)?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+
+        assert_eq!(top_docs.len(), 1);
+
+        let document: TantivyDocument = searcher.doc(top_docs[0].1)?;
+        let document = StoredDocument::try_from(document).unwrap();
+
+        assert_eq!(document.metadata.color, "red");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use tantivy::Index;
+use tantivy::query::AllQuery;
+use tantivy_derive::{IndexError, Schema, TypedIndex, index_version, migrate, tantivy_document};
+
+mod v1 {
+    use tantivy_derive::tantivy_document;
+
+    #[tantivy_document]
+    #[tantivy(version = 1)]
+    #[derive(Debug)]
+    pub struct Book {
+        #[tantivy(stored, text)]
+        pub title: String,
+        #[tantivy(stored, string)]
+        pub author: String,
+    }
+}
+
+#[tantivy_document]
+#[tantivy(version = 2)]
+#[derive(Debug, PartialEq)]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, text)]
+    pub authors: Vec<String>,
+    #[tantivy(stored, indexed)]
+    pub year: Option<u64>,
+}
+
+fn old_index() -> Result<TypedIndex<v1::Book>, Box<dyn std::error::Error>> {
+    let mut index = TypedIndex::<v1::Book>::create_in_ram()?;
+
+    for (title, author) in [
+        ("The Sea Wolf", "Jack London"),
+        ("White Fang", "Jack London"),
+        ("Moby Dick", "Herman Melville"),
+    ] {
+        index.add(&v1::Book {
+            title: title.to_string(),
+            author: author.to_string(),
+        })?;
+    }
+
+    index.commit()?;
+
+    Ok(index)
+}
+
+#[test]
+fn records_version() -> Result<(), Box<dyn std::error::Error>> {
+    let index = Index::create_in_ram(Book::schema());
+
+    assert_eq!(Book::VERSION, 2);
+    assert_eq!(index_version(&index)?, None);
+    assert_eq!(index_version(old_index()?.index())?, Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn migrates_documents() -> Result<(), Box<dyn std::error::Error>> {
+    let old_index = old_index()?;
+    let new_index = TypedIndex::<Book>::create_in_ram()?;
+
+    let count = migrate::<v1::Book, Book, _>(old_index.index(), new_index.index(), |old| Book {
+        title: old.title,
+        authors: vec![old.author],
+        year: None,
+    })?;
+
+    assert_eq!(count, 3);
+    assert_eq!(index_version(new_index.index())?, Some(2));
+
+    let reader = new_index.reader()?;
+    reader.reload()?;
+
+    let mut titles: Vec<String> = reader
+        .search(&AllQuery, 10)?
+        .into_iter()
+        .map(|(_, book)| {
+            assert_eq!(book.year, None);
+            book.title
+        })
+        .collect();
+    titles.sort();
+
+    assert_eq!(titles, ["Moby Dick", "The Sea Wolf", "White Fang"]);
+
+    Ok(())
+}
+
+#[test]
+fn rejects_other_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let old_index = old_index()?;
+    let new_index = Index::create_in_ram(Book::schema());
+
+    let result = migrate::<Book, Book, _>(old_index.index(), &new_index, |book| Book {
+        title: book.title,
+        authors: book.authors,
+        year: book.year,
+    });
+
+    assert!(matches!(
+        result,
+        Err(IndexError::Version {
+            expected: 2,
+            found: 1
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn rejects_other_destinations() -> Result<(), Box<dyn std::error::Error>> {
+    let old_index = old_index()?;
+    let transform = |old: v1::StoredBook| Book {
+        title: old.title,
+        authors: vec![old.author],
+        year: None,
+    };
+
+    let new_index = Index::create_in_ram(v1::Book::schema());
+    let result = migrate::<v1::Book, Book, _>(old_index.index(), &new_index, transform);
+
+    assert!(
+        matches!(result, Err(IndexError::Schema(ref mismatch)) if mismatch.missing == ["authors", "year"])
+    );
+
+    let result = migrate::<v1::Book, Book, _>(old_index.index(), old_index.index(), transform);
+
+    assert!(matches!(
+        result,
+        Err(IndexError::Version {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert_eq!(old_index.search(&AllQuery, 10)?.len(), 3);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::field_names;
+use tantivy::TantivyDocument;
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Author {
+    #[tantivy(stored, text)]
+    pub name: String,
+    #[tantivy(stored)]
+    pub born: u64,
+}
+
+#[tantivy_document]
+#[tantivy(separator = "_")]
+#[derive(Debug)]
+pub struct Publisher {
+    #[tantivy(stored, string)]
+    pub name: String,
+    #[tantivy(string)]
+    pub city: String,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored)]
+    pub author: Author,
+    #[tantivy(stored, rename = "by")]
+    pub publisher: Publisher,
+    #[tantivy(stored, flatten)]
+    pub editor: Author,
+}
+
+#[test]
+fn prefixes_nested_fields() {
+    assert_eq!(
+        field_names::<Book>(),
+        [
+            "title",
+            "author.name",
+            "author.born",
+            "by_name",
+            "by_city",
+            "name",
+            "born",
+        ]
+    );
+}
+
+#[test]
+fn resolves_nested_handles() {
+    let schema = Book::schema();
+    let fields = BookFields::from_schema(&schema).unwrap();
+
+    assert_eq!(fields.title, schema.get_field("title").unwrap());
+    assert_eq!(fields.author.name, schema.get_field("author.name").unwrap());
+    assert_eq!(fields.publisher.city, schema.get_field("by_city").unwrap());
+    assert_eq!(fields.editor.born, schema.get_field("born").unwrap());
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = Book {
+        title: "The Grapes of Wrath".to_string(),
+        author: Author {
+            name: "John Steinbeck".to_string(),
+            born: 1902,
+        },
+        publisher: Publisher {
+            name: "The Viking Press".to_string(),
+            city: "New York".to_string(),
+        },
+        editor: Author {
+            name: "Pascal Covici".to_string(),
+            born: 1885,
+        },
+    }
+    .into();
+
+    let book = StoredBook::try_from(document).unwrap();
+    let StoredAuthor { name, born } = book.author;
+    let StoredPublisher { name: publisher } = book.publisher;
+
+    assert_eq!(book.title, "The Grapes of Wrath");
+    assert_eq!(name, "John Steinbeck");
+    assert_eq!(born, 1902);
+    assert_eq!(publisher, "The Viking Press");
+    assert_eq!(book.editor.name, "Pascal Covici");
+    assert_eq!(book.editor.born, 1885);
+}
+
+#[test]
+fn reports_nested_field() {
+    let schema = Book::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("title").unwrap(), "East of Eden");
+    document.add_text(schema.get_field("author.name").unwrap(), "John Steinbeck");
+
+    let error = StoredBook::try_from(document).unwrap_err();
+
+    assert_eq!(error.field(), "author.born");
+}
+
+#[test]
+fn reports_nested_field_with_separator() {
+    let schema = Book::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("title").unwrap(), "East of Eden");
+    document.add_text(schema.get_field("author.name").unwrap(), "John Steinbeck");
+    document.add_u64(schema.get_field("author.born").unwrap(), 1902);
+
+    let error = StoredBook::try_from(document).unwrap_err();
+
+    assert_eq!(error.field(), "by_name");
+}
@@ -0,0 +1,122 @@
+use std::num::{NonZeroI32, NonZeroU16};
+use tantivy::TantivyDocument;
+use tantivy::schema::{FieldType, OwnedValue};
+use tantivy_derive::{Error, Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, indexed)]
+    pub delta: i64,
+    #[tantivy(stored)]
+    pub small: u8,
+    #[tantivy(stored)]
+    pub medium: u32,
+    #[tantivy(stored)]
+    pub size: usize,
+    #[tantivy(stored)]
+    pub offset: i16,
+    #[tantivy(stored)]
+    pub index: isize,
+    #[tantivy(stored)]
+    pub ratio: f32,
+    #[tantivy(stored)]
+    pub pages: NonZeroU16,
+    #[tantivy(stored)]
+    pub rank: NonZeroI32,
+}
+
+fn document() -> Document {
+    Document {
+        delta: -42,
+        small: 255,
+        medium: 70_000,
+        size: 1 << 40,
+        offset: -300,
+        index: -1,
+        ratio: 0.5,
+        pages: NonZeroU16::new(127).unwrap(),
+        rank: NonZeroI32::new(-3).unwrap(),
+    }
+}
+
+#[test]
+fn widens_into_tantivy_types() {
+    let schema = Document::schema();
+    let field_type = |name| {
+        schema
+            .get_field_entry(schema.get_field(name).unwrap())
+            .field_type()
+            .clone()
+    };
+
+    assert!(matches!(field_type("delta"), FieldType::I64(_)));
+    assert!(matches!(field_type("small"), FieldType::U64(_)));
+    assert!(matches!(field_type("size"), FieldType::U64(_)));
+    assert!(matches!(field_type("offset"), FieldType::I64(_)));
+    assert!(matches!(field_type("ratio"), FieldType::F64(_)));
+    assert!(matches!(field_type("pages"), FieldType::U64(_)));
+    assert!(matches!(field_type("rank"), FieldType::I64(_)));
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = document().into();
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.delta, -42);
+    assert_eq!(document.small, 255);
+    assert_eq!(document.medium, 70_000);
+    assert_eq!(document.size, 1 << 40);
+    assert_eq!(document.offset, -300);
+    assert_eq!(document.index, -1);
+    assert_eq!(document.ratio, 0.5);
+    assert_eq!(document.pages.get(), 127);
+    assert_eq!(document.rank.get(), -3);
+}
+
+fn replace(name: &str, value: OwnedValue) -> Error {
+    let schema = Document::schema();
+    let field = schema.get_field(name).unwrap();
+    let document: TantivyDocument = document().into();
+
+    let mut replaced = TantivyDocument::new();
+
+    for (f, v) in document.field_values() {
+        if f == field {
+            replaced.add_field_value(field, &value);
+        } else {
+            replaced.add_field_value(f, v);
+        }
+    }
+
+    StoredDocument::try_from(replaced).unwrap_err()
+}
+
+#[test]
+fn rejects_out_of_range_values() {
+    let error = replace("small", OwnedValue::U64(256));
+
+    assert!(matches!(
+        error,
+        Error::InvalidValue { ref field, ty: "u8", .. } if field == "small"
+    ));
+
+    let error = replace("offset", OwnedValue::I64(i64::MIN));
+
+    assert!(matches!(error, Error::InvalidValue { ty: "i16", .. }));
+
+    let error = replace("ratio", OwnedValue::F64(f64::MAX));
+
+    assert!(matches!(error, Error::InvalidValue { ty: "f32", .. }));
+}
+
+#[test]
+fn rejects_zero() {
+    let error = replace("pages", OwnedValue::U64(0));
+
+    assert!(matches!(
+        error,
+        Error::InvalidValue { ref field, .. } if field == "pages"
+    ));
+}
@@ -0,0 +1,136 @@
+use tantivy::collector::{Count, TopDocs};
+use tantivy::query::{BooleanQuery, Query};
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{Field, Schema, tantivy_document};
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+pub enum Status {
+    Open,
+    Closed,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Author {
+    #[tantivy(stored, string)]
+    pub name: String,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, indexed)]
+    pub published: i64,
+    #[tantivy(stored, indexed)]
+    pub status: Status,
+    #[tantivy(stored, string)]
+    pub tags: Vec<String>,
+    #[tantivy(stored)]
+    pub author: Author,
+}
+
+fn index() -> tantivy::Result<Index> {
+    let index = Index::create_in_ram(Document::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+
+    let documents = [
+        ("The Old Man and the Sea", 1952, Status::Open, "Hemingway"),
+        ("The Sea Wolf", 1904, Status::Closed, "London"),
+        ("The Call of the Wild", 1903, Status::Open, "London"),
+    ];
+
+    for (title, published, status, author) in documents {
+        writer.add_document(
+            Document {
+                title: title.to_string(),
+                published,
+                status,
+                tags: vec!["novel".to_string(), format!("y{published}")],
+                author: Author {
+                    name: author.to_string(),
+                },
+            }
+            .into(),
+        )?;
+    }
+
+    writer.commit()?;
+
+    Ok(index)
+}
+
+fn titles(index: &Index, query: &dyn Query) -> tantivy::Result<Vec<String>> {
+    let searcher = index.reader()?.searcher();
+    let mut titles = vec![];
+
+    for (_, address) in searcher.search(query, &TopDocs::with_limit(10))? {
+        let document: TantivyDocument = searcher.doc(address)?;
+        let document = StoredDocument::try_from(document).unwrap();
+
+        titles.push(document.title);
+    }
+
+    titles.sort();
+
+    Ok(titles)
+}
+
+#[test]
+fn matches_tokenized_text() -> tantivy::Result<()> {
+    let index = index()?;
+    let query = DocumentQuery::new(&index)?;
+
+    assert_eq!(
+        titles(&index, &*query.title().matches("SEA")?)?,
+        ["The Old Man and the Sea", "The Sea Wolf"]
+    );
+    assert!(titles(&index, &*query.title().eq("SEA"))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn encodes_values_through_fields() -> tantivy::Result<()> {
+    let index = index()?;
+    let query = DocumentQuery::new(&index)?;
+
+    assert_eq!(
+        titles(&index, &*query.published().between(1900, 1910))?,
+        ["The Call of the Wild", "The Sea Wolf"]
+    );
+    assert_eq!(
+        titles(&index, &*query.status().eq(Status::Closed))?,
+        ["The Sea Wolf"]
+    );
+    assert_eq!(
+        titles(&index, &*query.tags().eq("y1952"))?,
+        ["The Old Man and the Sea"]
+    );
+    assert_eq!(
+        titles(&index, &*query.author().name().eq("London"))?,
+        ["The Call of the Wild", "The Sea Wolf"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn composes_with_boolean_query() -> tantivy::Result<()> {
+    let index = index()?;
+    let query = DocumentQuery::new(&index)?;
+
+    let combined = BooleanQuery::intersection(vec![
+        query.author().name().eq("London"),
+        query.status().eq(Status::Open),
+    ]);
+
+    assert_eq!(titles(&index, &combined)?, ["The Call of the Wild"]);
+
+    let searcher = index.reader()?.searcher();
+
+    assert_eq!(searcher.search(&combined, &Count)?, 1);
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use tantivy::collector::TopDocs;
+use tantivy::query::QueryParser;
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Author {
+    #[tantivy(stored, text, search_default)]
+    pub name: String,
+}
+
+#[tantivy_document]
+#[tantivy(conjunction_by_default)]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text, search_default, boost = 2.0)]
+    pub title: String,
+    #[tantivy(text, search_default, fuzzy)]
+    pub body: String,
+    #[tantivy(stored, text, fuzzy(distance = 2, prefix))]
+    pub summary: String,
+    #[tantivy(stored)]
+    pub author: Author,
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Note {
+    #[tantivy(stored, text, search_default)]
+    pub text: String,
+}
+
+fn index() -> tantivy::Result<Index> {
+    let index = Index::create_in_ram(Document::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+
+    let documents = [
+        ("The Old Man and the Sea", "an old fisherman", "Hemingway"),
+        ("The Sea Wolf", "a ship and its captain", "London"),
+        ("Moby Dick", "a whale at sea", "Melville"),
+    ];
+
+    for (title, body, author) in documents {
+        writer.add_document(
+            Document {
+                title: title.to_string(),
+                body: body.to_string(),
+                summary: body.to_string(),
+                author: Author {
+                    name: author.to_string(),
+                },
+            }
+            .into(),
+        )?;
+    }
+
+    writer.commit()?;
+
+    Ok(index)
+}
+
+fn search(index: &Index, query: &str) -> tantivy::Result<Vec<String>> {
+    let query = Document::query_parser(index).parse_query(query)?;
+    let searcher = index.reader()?.searcher();
+    let mut titles = vec![];
+
+    for (_, address) in searcher.search(&query, &TopDocs::with_limit(10))? {
+        let document: TantivyDocument = searcher.doc(address)?;
+        let document = StoredDocument::try_from(document).unwrap();
+
+        titles.push(document.title);
+    }
+
+    Ok(titles)
+}
+
+#[test]
+fn searches_default_fields() -> tantivy::Result<()> {
+    let index = index()?;
+
+    assert_eq!(
+        search(&index, "london")?,
+        ["The Sea Wolf"],
+        "nested default fields are searched"
+    );
+    assert_eq!(
+        search(&index, "hemingway fisherman")?,
+        ["The Old Man and the Sea"]
+    );
+    assert!(
+        search(&index, "hemingway captain")?.is_empty(),
+        "all terms must match"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn applies_boosts() -> tantivy::Result<()> {
+    let index = index()?;
+    let searcher = index.reader()?.searcher();
+    let top_score = |query_parser: QueryParser| -> tantivy::Result<f32> {
+        let query = query_parser.parse_query("title:wolf")?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+
+        Ok(top_docs[0].0)
+    };
+
+    let boosted = top_score(Document::query_parser(&index))?;
+    let unboosted = top_score(QueryParser::for_index(&index, vec![]))?;
+
+    assert!((boosted - 2.0 * unboosted).abs() < 1e-6);
+
+    Ok(())
+}
+
+#[test]
+fn applies_fuzzy_settings() -> tantivy::Result<()> {
+    let index = index()?;
+
+    assert_eq!(search(&index, "fishermen")?, ["The Old Man and the Sea"]);
+    assert_eq!(search(&index, "summary:captian")?, ["The Sea Wolf"]);
+    assert!(search(&index, "title:wolves")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn skips_missing_fields() -> tantivy::Result<()> {
+    let index = index()?;
+    let query_parser = Note::query_parser(&index);
+
+    assert!(query_parser.parse_query("sea").is_err());
+
+    Ok(())
+}
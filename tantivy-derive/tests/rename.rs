@@ -0,0 +1,46 @@
+use tantivy::TantivyDocument;
+use tantivy_derive::{Schema, tantivy_document};
+
+#[tantivy_document]
+#[tantivy(rename_all = "camelCase")]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text, rename = "doc_title")]
+    pub title: String,
+    #[tantivy(stored, indexed)]
+    pub page_count: u64,
+    #[tantivy(stored)]
+    pub _publisher_name: String,
+}
+
+#[test]
+fn renames_fields() {
+    let schema = Document::schema();
+    let names: Vec<_> = schema
+        .fields()
+        .map(|(_, entry)| entry.name().to_string())
+        .collect();
+
+    assert_eq!(names, ["doc_title", "pageCount", "publisherName"]);
+
+    let fields = DocumentFields::from_schema(&schema).unwrap();
+
+    assert_eq!(fields.title, schema.get_field("doc_title").unwrap());
+    assert_eq!(fields.page_count, schema.get_field("pageCount").unwrap());
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = Document {
+        title: "Of Mice and Men".to_string(),
+        page_count: 107,
+        _publisher_name: "Covici Friede".to_string(),
+    }
+    .into();
+
+    let document = StoredDocument::try_from(document).unwrap();
+
+    assert_eq!(document.title, "Of Mice and Men");
+    assert_eq!(document.page_count, 107);
+    assert_eq!(document._publisher_name, "Covici Friede");
+}
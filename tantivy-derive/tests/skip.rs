@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use tantivy::TantivyDocument;
+use tantivy_derive::{Schema, tantivy_document};
+
+/// A runtime-only type that does not implement `tantivy_derive::Field`.
+#[derive(Debug, PartialEq)]
+pub struct Cache(Vec<String>);
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub verbose: bool,
+}
+
+fn default_config() -> Arc<Config> {
+    Arc::new(Config { verbose: true })
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub struct Book {
+    #[tantivy(skip)]
+    pub cache: Cache,
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(skip, default = "default_config")]
+    pub config: Arc<Config>,
+    #[tantivy(skip, default)]
+    pub hits: std::cell::Cell<u32>,
+    #[tantivy(stored, indexed, id)]
+    pub number: u64,
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    Opened {
+        #[tantivy(stored, string)]
+        path: String,
+        #[tantivy(skip, default)]
+        retries: u32,
+    },
+    Closed {
+        #[tantivy(skip)]
+        handle: Arc<Config>,
+        #[tantivy(stored, indexed)]
+        code: u64,
+    },
+}
+
+fn book() -> Book {
+    Book {
+        cache: Cache(vec![]),
+        title: "The Sea Wolf".to_string(),
+        config: Arc::new(Config::default()),
+        hits: std::cell::Cell::new(3),
+        number: 1,
+    }
+}
+
+#[test]
+fn leaves_out_skipped_fields() {
+    let schema = Book::schema();
+    let names: Vec<&str> = schema.fields().map(|(_, entry)| entry.name()).collect();
+
+    assert_eq!(names, ["title", "number"]);
+
+    let schema = Event::schema();
+    let names: Vec<&str> = schema.fields().map(|(_, entry)| entry.name()).collect();
+
+    assert_eq!(names, ["type", "path", "code"]);
+}
+
+#[test]
+fn fills_in_skipped_fields() {
+    let document: TantivyDocument = book().into();
+
+    assert_eq!(
+        StoredBook::try_from(document),
+        Ok(StoredBook {
+            title: "The Sea Wolf".to_string(),
+            config: Arc::new(Config { verbose: true }),
+            hits: std::cell::Cell::new(0),
+            number: 1,
+        })
+    );
+}
+
+#[test]
+fn skips_fields_of_variants() {
+    let document: TantivyDocument = Event::Closed {
+        handle: Arc::new(Config::default()),
+        code: 2,
+    }
+    .into();
+
+    assert_eq!(
+        StoredEvent::try_from(document),
+        Ok(StoredEvent::Closed { code: 2 })
+    );
+
+    let document: TantivyDocument = Event::Opened {
+        path: "/tmp".to_string(),
+        retries: 3,
+    }
+    .into();
+
+    assert_eq!(
+        StoredEvent::try_from(document),
+        Ok(StoredEvent::Opened {
+            path: "/tmp".to_string(),
+            retries: 0,
+        })
+    );
+}
+
+#[test]
+fn skips_fields_of_handles() -> tantivy::Result<()> {
+    let fields = BookFields::from_schema(&Book::schema())?;
+
+    assert_eq!(Book::schema().get_field_name(fields.number), "number");
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use tantivy::collector::Count;
+use tantivy::schema::{Facet, FieldType, OwnedValue};
+use tantivy::{Index, IndexWriter, TantivyDocument};
+use tantivy_derive::{Error, Field, FieldTable, Identifiable, Schema, tantivy_document};
+
+#[derive(Clone, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct Sku(String);
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct UserId {
+    value: u64,
+}
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct Id<T>(T);
+
+#[derive(Clone, Copy, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct Buyer(UserId);
+
+#[derive(Clone, Debug, Field, PartialEq)]
+#[tantivy(transparent)]
+pub struct Tags(Vec<String>);
+
+#[derive(Clone, Debug, Field, PartialEq)]
+#[tantivy(transparent, from_target)]
+pub struct Category(Facet);
+
+impl From<String> for Category {
+    fn from(path: String) -> Self {
+        Self(Facet::from(path.as_str()))
+    }
+}
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Order {
+    #[tantivy(stored, string, id)]
+    pub sku: Sku,
+    #[tantivy(stored, indexed, fast)]
+    pub user: UserId,
+    #[tantivy(stored, indexed)]
+    pub reviewers: Vec<UserId>,
+    #[tantivy(stored, indexed)]
+    pub parent: Option<Id<i64>>,
+    #[tantivy(stored, indexed)]
+    pub buyer: Buyer,
+    #[tantivy(stored, string)]
+    pub tags: Tags,
+    #[tantivy(stored)]
+    pub category: Category,
+}
+
+fn order() -> Order {
+    Order {
+        sku: Sku("ABC-1".to_string()),
+        user: UserId { value: 7 },
+        reviewers: vec![UserId { value: 1 }, UserId { value: 2 }],
+        parent: Some(Id(-1)),
+        buyer: Buyer(UserId { value: 3 }),
+        tags: Tags(vec!["gift".to_string(), "sale".to_string()]),
+        category: Category(Facet::from("/books/fiction")),
+    }
+}
+
+#[test]
+fn uses_inner_type() {
+    let schema = Order::schema();
+    let field_type = |name| {
+        schema
+            .get_field_entry(schema.get_field(name).unwrap())
+            .field_type()
+            .clone()
+    };
+
+    assert!(matches!(field_type("sku"), FieldType::Str(_)));
+    assert!(matches!(field_type("user"), FieldType::U64(_)));
+    assert!(matches!(field_type("reviewers"), FieldType::U64(_)));
+    assert!(matches!(field_type("parent"), FieldType::I64(_)));
+    assert!(matches!(field_type("buyer"), FieldType::U64(_)));
+    assert!(matches!(field_type("tags"), FieldType::Str(_)));
+    assert!(matches!(field_type("category"), FieldType::Facet(_)));
+}
+
+#[test]
+fn stores_inner_values() {
+    let schema = Order::schema();
+    let document: TantivyDocument = order().into();
+    let values = |name| {
+        document
+            .get_all(schema.get_field(name).unwrap())
+            .map(OwnedValue::from)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(values("sku"), [OwnedValue::Str("ABC-1".into())]);
+    assert_eq!(values("user"), [OwnedValue::U64(7)]);
+    assert_eq!(
+        values("reviewers"),
+        [OwnedValue::U64(1), OwnedValue::U64(2)]
+    );
+    assert_eq!(values("parent"), [OwnedValue::I64(-1)]);
+    assert_eq!(values("buyer"), [OwnedValue::U64(3)]);
+    assert_eq!(
+        values("tags"),
+        [
+            OwnedValue::Str("gift".into()),
+            OwnedValue::Str("sale".into())
+        ]
+    );
+}
+
+#[test]
+fn round_trip() {
+    let document: TantivyDocument = order().into();
+    let order = StoredOrder::try_from(document).unwrap();
+
+    assert_eq!(order.sku, Sku("ABC-1".to_string()));
+    assert_eq!(order.user, UserId { value: 7 });
+    assert_eq!(order.reviewers, [UserId { value: 1 }, UserId { value: 2 }]);
+    assert_eq!(order.parent, Some(Id(-1)));
+    assert_eq!(order.buyer, Buyer(UserId { value: 3 }));
+    assert_eq!(
+        order.tags,
+        Tags(vec!["gift".to_string(), "sale".to_string()])
+    );
+    assert_eq!(order.category, Category(Facet::from("/books/fiction")));
+}
+
+#[test]
+fn reports_errors_of_inner_type() {
+    let schema = Order::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("sku").unwrap(), "ABC-1");
+    document.add_text(schema.get_field("user").unwrap(), "seven");
+
+    assert!(matches!(
+        StoredOrder::try_from(document),
+        Err(Error::UnexpectedType { ref field, .. }) if field == "user"
+    ));
+}
+
+#[test]
+fn queries_newtypes() -> tantivy::Result<()> {
+    let index = Index::create_in_ram(Order::schema());
+    let mut writer: IndexWriter = index.writer(15_000_000)?;
+    Order::upsert(&writer, &FieldTable::default(), &order())?;
+    writer.commit()?;
+
+    let searcher = index.reader()?.searcher();
+    let query = OrderQuery::new(&index)?;
+
+    assert_eq!(
+        searcher.search(&query.sku().eq(Sku("ABC-1".to_string())), &Count)?,
+        1
+    );
+    assert_eq!(
+        searcher.search(
+            &query
+                .user()
+                .between(UserId { value: 5 }, UserId { value: 10 }),
+            &Count
+        )?,
+        1
+    );
+    assert_eq!(
+        searcher.search(&query.reviewers().eq(UserId { value: 3 }), &Count)?,
+        0
+    );
+    assert_eq!(searcher.search(&query.tags().eq("sale"), &Count)?, 1);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use tantivy::Index;
+use tantivy::query::AllQuery;
+use tantivy_derive::{IndexError, Schema, TypedIndex, tantivy_document};
+
+#[tantivy_document]
+#[tantivy(analyzer(name = "lower", tokenizer = "whitespace", filters(lowercase)))]
+#[derive(Debug, PartialEq)]
+pub struct Document {
+    #[tantivy(stored, text, tokenizer = "lower", search_default)]
+    pub title: String,
+    #[tantivy(stored, indexed)]
+    pub year: u64,
+}
+
+#[tantivy_document]
+#[tantivy(analyzer(name = "lower", tokenizer = "whitespace", filters(lowercase)))]
+#[tantivy(version = 1)]
+pub struct RevisedDocument {
+    #[tantivy(stored, text, tokenizer = "lower", search_default)]
+    pub title: String,
+    #[tantivy(stored, indexed)]
+    pub year: u64,
+}
+
+#[tantivy_document]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+fn document(title: &str, year: u64) -> Document {
+    Document {
+        title: title.to_string(),
+        year,
+    }
+}
+
+#[test]
+fn adds_and_searches() -> Result<(), Box<dyn std::error::Error>> {
+    let mut index = TypedIndex::<Document>::create_in_ram()?;
+
+    index.add(&document("The Old Man and the Sea", 1952))?;
+    index.add(&document("The Sea Wolf", 1904))?;
+
+    assert!(index.search(&AllQuery, 10)?.is_empty());
+
+    index.commit()?;
+
+    let query = Document::query_parser(index.index()).parse_query("WOLF")?;
+    let results = index.search(&*query, 10)?;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].1,
+        StoredDocument {
+            title: "The Sea Wolf".to_string(),
+            year: 1904,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn searches_commits_of_other_writers() -> Result<(), Box<dyn std::error::Error>> {
+    let index = TypedIndex::<Document>::create_in_ram()?;
+
+    assert!(index.search(&AllQuery, 10)?.is_empty());
+
+    let mut writer = index.writer(15_000_000)?;
+    writer.add(&document("White Fang", 1906))?;
+    writer.commit()?;
+
+    let results = index.search(&AllQuery, 10)?;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1.title, "White Fang");
+
+    Ok(())
+}
+
+#[test]
+fn opens_existing_index() -> Result<(), Box<dyn std::error::Error>> {
+    let directory = tempfile::TempDir::new()?;
+
+    {
+        let index = TypedIndex::<Document>::create_in_dir(&directory)?;
+        let mut writer = index.writer(15_000_000)?;
+
+        writer.add(&document("The Call of the Wild", 1903))?;
+        writer.commit()?;
+    }
+
+    let index = TypedIndex::<Document>::open(&directory)?;
+    let reader = index.reader()?;
+    let results = reader.search(&AllQuery, 10)?;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1.title, "The Call of the Wild");
+
+    Ok(())
+}
+
+#[test]
+fn rejects_other_schemas_and_versions() -> Result<(), Box<dyn std::error::Error>> {
+    let result = TypedIndex::<Document>::from_index(Index::create_in_ram(Book::schema()));
+
+    assert!(matches!(result, Err(IndexError::Schema(_))));
+
+    let index = TypedIndex::<Document>::create_in_ram()?;
+    let mut writer = index.writer(15_000_000)?;
+    writer.add(&document("White Fang", 1906))?;
+    writer.commit()?;
+
+    let result = TypedIndex::<RevisedDocument>::from_index(index.index().clone());
+
+    assert!(matches!(
+        result,
+        Err(IndexError::Version {
+            expected: 1,
+            found: 0
+        })
+    ));
+
+    Ok(())
+}
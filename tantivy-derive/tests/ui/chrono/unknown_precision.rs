@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, precision = "minutes")]
+    pub published: DateTime<Utc>,
+}
+
+fn main() {}
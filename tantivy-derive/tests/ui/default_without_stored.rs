@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(indexed, default)]
+    pub pages: u64,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub enum Event {
+    Click {
+        #[tantivy(stored, string)]
+        url: String,
+    },
+    View {
+        #[tantivy(stored, string)]
+        url: String,
+    },
+}
+
+fn main() {}
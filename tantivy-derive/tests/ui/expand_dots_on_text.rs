@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, text, expand_dots)]
+    pub title: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(text, fuzzy(distance = 3))]
+    pub title: String,
+}
+
+fn main() {}
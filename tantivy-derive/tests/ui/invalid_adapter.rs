@@ -0,0 +1,13 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, as_string, via = "u64")]
+    pub both: u32,
+    #[tantivy(stored, via = "u64")]
+    pub many: Vec<u32>,
+    #[tantivy(stored, string, id, as_string)]
+    pub id: std::net::Ipv4Addr,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(text, id)]
+    pub title: String,
+    #[tantivy(stored, id)]
+    pub year: u64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tantivy_derive::Field;
+
+#[derive(Field)]
+pub struct Sku(String);
+
+#[derive(Field)]
+#[tantivy(transparent)]
+pub struct Range(u64, u64);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+#[tantivy(view(name = "Summary", fields(title, body, author)))]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(text)]
+    pub body: String,
+}
+
+fn main() {}
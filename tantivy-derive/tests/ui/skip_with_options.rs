@@ -0,0 +1,11 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(skip, stored, indexed)]
+    pub hits: u64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document(name = "BookRecord")]
+#[tantivy(target = "StoredBook")]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub count: u64,
+    #[tantivy(indexed, tokenizer = "raw")]
+    pub score: Option<f64>,
+}
+
+fn main() {}
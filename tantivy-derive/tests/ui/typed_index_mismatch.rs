@@ -0,0 +1,24 @@
+use tantivy_derive::{TypedIndex, tantivy_document};
+
+#[tantivy_document]
+pub struct Book {
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+#[tantivy_document]
+pub struct Author {
+    #[tantivy(stored, text)]
+    pub name: String,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let index = TypedIndex::<Book>::create_in_ram()?;
+    let writer = index.writer(15_000_000)?;
+
+    writer.add(&Author {
+        name: "Ernest Hemingway".to_string(),
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use tantivy_derive::Document;
+
+#[derive(Document)]
+pub struct Document {
+    #[tantivy(storde, text)]
+    pub title: String,
+}
+
+fn main() {}
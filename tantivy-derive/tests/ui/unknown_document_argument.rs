@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document(nme = "Stored")]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(text, index_option = "positions")]
+    pub body: String,
+}
+
+fn main() {}
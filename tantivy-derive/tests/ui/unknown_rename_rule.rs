@@ -0,0 +1,10 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+#[tantivy(rename_all = "camel_case")]
+pub struct Document {
+    #[tantivy(stored, text)]
+    pub title: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tantivy_derive::Field;
+
+#[derive(Field)]
+#[tantivy(repr = "i64")]
+pub enum Status {
+    Draft,
+    Published,
+}
+
+fn main() {}
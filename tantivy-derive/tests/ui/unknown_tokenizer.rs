@@ -0,0 +1,10 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+#[tantivy(analyzer(name = "en_full", filters(lowercase)))]
+pub struct Document {
+    #[tantivy(text, tokenizer = "en_ful")]
+    pub title: String,
+}
+
+fn main() {}
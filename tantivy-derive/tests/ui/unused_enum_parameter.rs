@@ -0,0 +1,12 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub enum Event<P> {
+    Created {
+        #[tantivy(indexed)]
+        payload: P,
+    },
+    Deleted,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use tantivy_derive::tantivy_document;
+
+#[tantivy_document]
+pub struct Document {
+    #[tantivy(stored, string, id, with = "path")]
+    pub path: std::path::PathBuf,
+}
+
+fn main() {}
@@ -0,0 +1,72 @@
+use tantivy::schema::{
+    FAST, INDEXED, STORED, STRING, Schema as TantivySchema, TextFieldIndexing, TextOptions, Type,
+};
+use tantivy_derive::{OptionChange, Schema, SchemaMismatch, TypeChange, tantivy_document};
+
+#[tantivy_document]
+#[derive(Debug)]
+pub struct Document {
+    #[tantivy(stored, text, tokenizer = "en_stem")]
+    pub title: String,
+    #[tantivy(stored, string, fast)]
+    pub category: String,
+    #[tantivy(stored, indexed)]
+    pub year: u64,
+    #[tantivy(stored)]
+    pub rating: f64,
+}
+
+#[test]
+fn matching_schema() {
+    assert_eq!(Document::verify_schema(&Document::schema()), Ok(()));
+}
+
+#[test]
+fn mismatching_schema() {
+    let mut builder = TantivySchema::builder();
+    builder.add_text_field(
+        "title",
+        TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default())
+            .set_stored(),
+    );
+    builder.add_text_field("category", STRING | STORED);
+    builder.add_i64_field("year", INDEXED | STORED);
+    builder.add_u64_field("views", FAST);
+    let schema = builder.build();
+
+    let mismatch = Document::verify_schema(&schema).unwrap_err();
+
+    assert_eq!(
+        mismatch,
+        SchemaMismatch {
+            missing: vec!["rating".to_string()],
+            extra: vec!["views".to_string()],
+            type_changes: vec![TypeChange {
+                field: "year".to_string(),
+                expected: Type::U64,
+                found: Type::I64,
+            }],
+            option_changes: vec![
+                OptionChange {
+                    field: "title".to_string(),
+                    option: "tokenizer",
+                    expected: Some("en_stem".to_string()),
+                    found: Some("default".to_string()),
+                },
+                OptionChange {
+                    field: "category".to_string(),
+                    option: "fast",
+                    expected: Some("true".to_string()),
+                    found: Some("false".to_string()),
+                },
+            ],
+        }
+    );
+    assert_eq!(
+        mismatch.to_string(),
+        "schema mismatch: field `rating` is missing, field `views` is unexpected, field `year` \
+        has type `I64` instead of `U64`, field `title` has `tokenizer` set to default instead of \
+        en_stem, field `category` has `fast` set to false instead of true"
+    );
+}
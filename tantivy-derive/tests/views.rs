@@ -0,0 +1,125 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, Utc};
+use tantivy::query::AllQuery;
+use tantivy::{Index, TantivyDocument};
+use tantivy_derive::{FieldTable, Schema, TypedIndex, View, tantivy_document};
+
+#[tantivy_document]
+#[tantivy(
+    view(name = "Summary", fields(title, published)),
+    view(name = "Body", fields(body))
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Article {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored, text)]
+    pub body: String,
+    #[tantivy(text)]
+    pub keywords: Vec<String>,
+    #[tantivy(stored, indexed)]
+    pub published: DateTime<Utc>,
+}
+
+#[tantivy_document(name = "ArticleRecord")]
+#[tantivy(view(name = "Title", fields(title)))]
+#[derive(Debug, PartialEq)]
+pub struct NamedArticle {
+    #[tantivy(stored, text)]
+    pub title: String,
+    #[tantivy(stored)]
+    pub views: u64,
+}
+
+fn article() -> Article {
+    Article {
+        title: "The Sea Wolf".to_string(),
+        body: "Humphrey van Weyden is a literary critic.".to_string(),
+        keywords: vec!["sea".to_string()],
+        published: DateTime::from_timestamp(1_000_000_000, 0).unwrap(),
+    }
+}
+
+#[test]
+fn converts_views() {
+    let article = article();
+    let document: TantivyDocument = article.clone().into();
+
+    assert_eq!(
+        ArticleSummary::try_from(document.clone()),
+        Ok(ArticleSummary {
+            title: article.title,
+            published: article.published,
+        })
+    );
+    assert_eq!(
+        ArticleBody::try_from(document),
+        Ok(ArticleBody { body: article.body })
+    );
+}
+
+#[test]
+fn converts_views_with_field_table() -> tantivy::Result<()> {
+    let mut builder = tantivy::schema::Schema::builder();
+    builder.add_u64_field("extra", tantivy::schema::STORED);
+    for (_, entry) in Article::schema().fields() {
+        builder.add_field(entry.clone());
+    }
+    let schema = builder.build();
+
+    let fields = FieldTable::new::<Article>(&schema);
+    let document = article().to_document(&fields);
+
+    assert_eq!(
+        ArticleSummary::from_document(&document, &fields)
+            .unwrap()
+            .title,
+        "The Sea Wolf"
+    );
+
+    let index = Index::create_in_ram(schema);
+    let mut writer: tantivy::IndexWriter = index.writer(15_000_000)?;
+    writer.add_document(document)?;
+    writer.commit()?;
+
+    Ok(())
+}
+
+#[test]
+fn searches_views() -> Result<(), Box<dyn std::error::Error>> {
+    let mut index = TypedIndex::<Article>::create_in_ram()?;
+
+    index.add(&article())?;
+    index.commit()?;
+
+    let results = index.search_view::<ArticleSummary>(&AllQuery, 10)?;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].1.title, "The Sea Wolf");
+
+    Ok(())
+}
+
+#[test]
+fn named_stored_struct() {
+    let document: TantivyDocument = NamedArticle {
+        title: "White Fang".to_string(),
+        views: 3,
+    }
+    .into();
+
+    assert_eq!(
+        ArticleRecord::try_from(document.clone()),
+        Ok(ArticleRecord {
+            title: "White Fang".to_string(),
+            views: 3,
+        })
+    );
+    assert_eq!(
+        NamedArticleTitle::try_from(document),
+        Ok(NamedArticleTitle {
+            title: "White Fang".to_string(),
+        })
+    );
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tantivy::TantivyDocument;
+use tantivy::schema::{FieldType, OwnedValue, SchemaBuilder, Value as _};
+use tantivy_derive::{Error, FieldOptions, Schema, tantivy_document};
+
+mod millis {
+    use super::*;
+
+    pub fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+        builder.add_u64_field(name, options);
+    }
+
+    pub fn insert(document: &mut TantivyDocument, field: tantivy::schema::Field, value: &Duration) {
+        document.add_u64(field, value.as_millis() as u64);
+    }
+
+    pub fn map_value(value: &OwnedValue) -> Result<Duration, Error> {
+        match *value {
+            OwnedValue::U64(millis) => Ok(Duration::from_millis(millis)),
+            ref value => Err(Error::unexpected_type("u64", value)),
+        }
+    }
+}
+
+mod path {
+    use super::*;
+
+    pub fn add_field(builder: &mut SchemaBuilder, name: &str, options: FieldOptions) {
+        builder.add_text_field(name, options);
+    }
+
+    pub fn insert(document: &mut TantivyDocument, field: tantivy::schema::Field, value: &Path) {
+        document.add_text(field, value.to_string_lossy());
+    }
+
+    pub fn map_value(value: &OwnedValue) -> Result<PathBuf, Error> {
+        value
+            .as_str()
+            .map(PathBuf::from)
+            .ok_or_else(|| Error::unexpected_type("str", value))
+    }
+}
+
+#[tantivy_document]
+#[tantivy(view(name = "Summary", fields(path)))]
+#[derive(Debug, PartialEq)]
+pub struct Recording {
+    #[tantivy(stored, string, with = "path")]
+    pub path: PathBuf,
+    #[tantivy(stored, indexed, fast, with = "millis")]
+    pub length: Duration,
+    #[tantivy(stored, indexed)]
+    pub plays: u64,
+    #[tantivy(indexed, with = "millis")]
+    pub delay: Duration,
+}
+
+#[tantivy_document]
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    Played {
+        #[tantivy(stored, indexed, with = "millis")]
+        position: Duration,
+    },
+    Stopped,
+}
+
+fn recording() -> Recording {
+    Recording {
+        path: PathBuf::from("/music/sea.flac"),
+        length: Duration::from_millis(1500),
+        plays: 3,
+        delay: Duration::ZERO,
+    }
+}
+
+#[test]
+fn adds_fields_with_module() {
+    let schema = Recording::schema();
+    let length = schema.get_field_entry(schema.get_field("length").unwrap());
+
+    assert!(matches!(length.field_type(), FieldType::U64(_)));
+    assert!(length.is_fast());
+    assert_eq!(schema.get_field("plays").unwrap().field_id(), 2);
+}
+
+#[test]
+fn converts_fields_with_module() {
+    let document: TantivyDocument = recording().into();
+
+    assert_eq!(
+        StoredRecording::try_from(document.clone()),
+        Ok(StoredRecording {
+            path: PathBuf::from("/music/sea.flac"),
+            length: Duration::from_millis(1500),
+            plays: 3,
+        })
+    );
+    assert_eq!(
+        RecordingSummary::try_from(document),
+        Ok(RecordingSummary {
+            path: PathBuf::from("/music/sea.flac"),
+        })
+    );
+
+    let document: TantivyDocument = Event::Played {
+        position: Duration::from_millis(20),
+    }
+    .into();
+
+    assert_eq!(
+        StoredEvent::try_from(document),
+        Ok(StoredEvent::Played {
+            position: Duration::from_millis(20),
+        })
+    );
+}
+
+#[test]
+fn reports_errors_of_module() {
+    let schema = Recording::schema();
+    let mut document = TantivyDocument::new();
+    document.add_text(schema.get_field("path").unwrap(), "/music/sea.flac");
+    document.add_text(schema.get_field("length").unwrap(), "long");
+
+    assert_eq!(
+        StoredRecording::try_from(document),
+        Err(Error::UnexpectedType {
+            field: "length".to_string(),
+            expected: "u64",
+            found: "str",
+        })
+    );
+}
+
+#[test]
+fn resolves_handles_with_module() -> tantivy::Result<()> {
+    let schema = Recording::schema();
+    let fields = RecordingFields::from_schema(&schema)?;
+
+    assert_eq!(schema.get_field_name(fields.length), "length");
+
+    Ok(())
+}